@@ -1,5 +1,6 @@
 pub mod coefficient;
 
+use serde::{Deserialize, Serialize};
 use std::fmt::Display;
 
 /// Trait for all resources
@@ -101,7 +102,8 @@ impl Resource for Food {}
 /// money.add(10);
 /// assert_eq!(money.get(), 10);
 /// ```
-#[derive(Clone, Default)]
+#[derive(Clone, Default, Debug, PartialEq, PartialOrd, Serialize, Deserialize)]
+#[serde(transparent)]
 pub struct Money {
     amount: i64,
 }
@@ -203,7 +205,8 @@ impl Resource for Money {}
 /// work_force.add(10);
 /// assert_eq!(work_force.get(), 10);
 /// ```
-#[derive(Clone, Default)]
+#[derive(Clone, Default, Debug, PartialEq, PartialOrd, Serialize, Deserialize)]
+#[serde(transparent)]
 pub struct WorkForce {
     amount: u64,
 }
@@ -296,7 +299,8 @@ impl Resource for WorkForce {}
 /// ores.add_uranium(10);
 /// assert_eq!(ores.get_uranium(), 10);
 /// ```
-#[derive(Clone, Default)]
+#[derive(Clone, Default, Debug, PartialEq, PartialOrd, Serialize, Deserialize)]
+#[serde(default)]
 pub struct Ores {
     uranium: u64,
     rate_metals: u64,
@@ -439,7 +443,8 @@ impl Display for Ores {
 }
 impl Resource for Ores {}
 
-#[derive(Clone, Default)]
+#[derive(Clone, Default, Debug, PartialEq, PartialOrd, Serialize, Deserialize)]
+#[serde(default)]
 pub struct RefinedProduct {
     alloys: u64,
     chips: u64,
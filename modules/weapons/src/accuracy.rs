@@ -0,0 +1,153 @@
+//! This module define the accuracy of weapons
+//!
+//! The impact points of a weapon follow a circular normal distribution around the aim point. Its
+//! spread is defined by the circular error probable (CEP): the radius of the circle in which half
+//! of the rounds land.
+
+use crate::TargetKind;
+use serde::{Deserialize, Serialize};
+
+/// Define how accurate a weapon is
+#[derive(Clone, Copy, Default, Debug, Serialize, Deserialize, PartialEq, PartialOrd)]
+pub struct Accuracy {
+    /// The circular error probable in meters, whatever the distance
+    #[serde(default)]
+    pub cep: f32,
+    /// The circular error probable in meters added for each kilometer to the target
+    #[serde(default)]
+    pub dispersion: f32,
+    /// The quality of the guidance, from 0.0 (unguided) to 1.0 (perfect)
+    ///
+    /// A guided weapon corrects its trajectory, which reduces the circular error probable.
+    #[serde(default)]
+    pub guidance: f32,
+}
+
+impl Accuracy {
+    /// Get the circular error probable in meters at a distance in kilometers
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use weapons::accuracy::Accuracy;
+    ///
+    /// let accuracy = Accuracy {
+    ///     cep: 2.0,
+    ///     dispersion: 1.0,
+    ///     guidance: 0.5,
+    /// };
+    /// assert_eq!(accuracy.cep_at(4.0), 3.0);
+    /// ```
+    pub fn cep_at(&self, distance: f32) -> f32 {
+        let spread = self.cep.max(0.0) + self.dispersion.max(0.0) * distance.max(0.0);
+        spread * (1.0 - self.guidance.clamp(0.0, 1.0))
+    }
+
+    /// Get the probability (from 0.0 to 1.0) to hit a target at a distance in kilometers
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use weapons::accuracy::Accuracy;
+    /// use weapons::TargetKind;
+    ///
+    /// let accuracy = Accuracy {
+    ///     cep: 3.5,
+    ///     ..Default::default()
+    /// };
+    /// // half of the rounds land closer than the CEP
+    /// assert_eq!(accuracy.hit_probability(0.0, TargetKind::Tank), 0.5);
+    ///
+    /// let perfect = Accuracy::default();
+    /// assert_eq!(perfect.hit_probability(10.0, TargetKind::Infantry), 1.0);
+    /// ```
+    pub fn hit_probability(&self, distance: f32, target: TargetKind) -> f32 {
+        let cep = self.cep_at(distance);
+        if cep <= 0.0 {
+            return 1.0;
+        }
+
+        let ratio = target.radius() / cep;
+        1.0 - 0.5f32.powf(ratio * ratio)
+    }
+
+    /// Tell if a shot hits its target, from a roll between 0.0 and 1.0
+    ///
+    /// The roll is given by the caller so the result stays deterministic.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use weapons::accuracy::Accuracy;
+    /// use weapons::TargetKind;
+    ///
+    /// let accuracy = Accuracy {
+    ///     cep: 3.5,
+    ///     ..Default::default()
+    /// };
+    /// assert!(accuracy.hits(0.0, TargetKind::Tank, 0.2));
+    /// assert!(!accuracy.hits(0.0, TargetKind::Tank, 0.8));
+    /// ```
+    pub fn hits(&self, distance: f32, target: TargetKind, roll: f32) -> bool {
+        roll < self.hit_probability(distance, target)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    #[test]
+    fn test_probability_decreases_with_distance() {
+        use super::*;
+
+        let accuracy = Accuracy {
+            cep: 1.0,
+            dispersion: 2.0,
+            guidance: 0.0,
+        };
+        let close = accuracy.hit_probability(0.5, TargetKind::Vehicle);
+        let far = accuracy.hit_probability(5.0, TargetKind::Vehicle);
+        assert!(close > far);
+        assert!(far > 0.0);
+    }
+
+    #[test]
+    fn test_bigger_targets_are_easier_to_hit() {
+        use super::*;
+
+        let accuracy = Accuracy {
+            cep: 20.0,
+            ..Default::default()
+        };
+        assert!(
+            accuracy.hit_probability(1.0, TargetKind::Ship)
+                > accuracy.hit_probability(1.0, TargetKind::Infantry)
+        );
+    }
+
+    #[test]
+    fn test_guidance_improves_accuracy() {
+        use super::*;
+
+        let unguided = Accuracy {
+            cep: 50.0,
+            dispersion: 10.0,
+            guidance: 0.0,
+        };
+        let guided = Accuracy {
+            guidance: 0.9,
+            ..unguided
+        };
+        assert!(
+            guided.hit_probability(10.0, TargetKind::Building)
+                > unguided.hit_probability(10.0, TargetKind::Building)
+        );
+        assert_eq!(
+            Accuracy {
+                guidance: 1.0,
+                ..unguided
+            }
+            .hit_probability(10.0, TargetKind::Infantry),
+            1.0
+        );
+    }
+}
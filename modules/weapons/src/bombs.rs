@@ -0,0 +1,214 @@
+//! This module define aerial bombs dropped by planes and helicopters
+
+use crate::{Damages, Weapon, WeaponCategory, WeaponInformations};
+use serde::{Deserialize, Serialize};
+
+/// Enumeration representing different types of aerial bombs.
+#[derive(Clone, Debug, PartialEq, PartialOrd, Copy, Serialize, Deserialize)]
+#[repr(u8)]
+pub enum BombType {
+    /// Unguided bomb, follows a ballistic trajectory once dropped.
+    FreeFall = 0,
+    /// Bomb steered toward its target by a laser or GPS guidance kit.
+    Guided = 1,
+    /// Bomb releasing many submunitions over a wide area.
+    ///
+    /// Very effective against infantry and light vehicles
+    Cluster = 2,
+    /// Heavy bomb designed to penetrate deep into the ground or concrete before exploding.
+    ///
+    /// Very effective against buildings and fortifications
+    BunkerBuster = 3,
+}
+
+impl TryFrom<i64> for BombType {
+    type Error = ();
+
+    fn try_from(value: i64) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Self::FreeFall),
+            1 => Ok(Self::Guided),
+            2 => Ok(Self::Cluster),
+            3 => Ok(Self::BunkerBuster),
+            _ => Err(()),
+        }
+    }
+}
+
+/// An aerial bomb
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Bomb {
+    #[serde(deserialize_with = "crate::deserialize_id")]
+    bomb_type: BombType,
+    /// The drag coefficient of the bomb, a higher drag slows the fall of the bomb
+    #[serde(default)]
+    drag: f32,
+    /// The depth of concrete in meters the bomb can go through before exploding
+    ///
+    /// See [crate::combat::CONCRETE_TO_ARMOR] for its equivalent in millimeters of armor
+    #[serde(default, alias = "penetration")]
+    concrete_penetration: f32,
+
+    informations: WeaponInformations,
+    damages: Damages,
+}
+
+impl Bomb {
+    /// Create a new bomb
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use weapons::bombs::{Bomb, BombType};
+    ///
+    /// let bomb = Bomb::new(BombType::Guided);
+    /// assert_eq!(bomb.get_bomb_type(), BombType::Guided);
+    /// assert_eq!(bomb.get_drag(), 0.0);
+    /// assert_eq!(bomb.get_concrete_penetration(), 0.0);
+    /// ```
+    pub fn new(bomb_type: BombType) -> Self {
+        Self {
+            bomb_type,
+            drag: 0.0,
+            concrete_penetration: 0.0,
+
+            informations: WeaponInformations::default(),
+            damages: Damages::default(),
+        }
+    }
+
+    /// Get the type of the bomb
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use weapons::bombs::{Bomb, BombType};
+    ///
+    /// let bomb = Bomb::new(BombType::Cluster);
+    /// assert_eq!(bomb.get_bomb_type(), BombType::Cluster);
+    /// ```
+    pub fn get_bomb_type(&self) -> BombType {
+        self.bomb_type
+    }
+
+    /// Define a new type for the bomb
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use weapons::bombs::{Bomb, BombType};
+    ///
+    /// let mut bomb = Bomb::new(BombType::FreeFall);
+    /// bomb.set_bomb_type(BombType::BunkerBuster);
+    /// assert_eq!(bomb.get_bomb_type(), BombType::BunkerBuster);
+    /// ```
+    pub fn set_bomb_type(&mut self, bomb_type: BombType) {
+        self.bomb_type = bomb_type;
+    }
+
+    /// Get the drag coefficient of the bomb
+    pub fn get_drag(&self) -> f32 {
+        self.drag
+    }
+
+    /// Define a new drag coefficient for the bomb
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use weapons::bombs::{Bomb, BombType};
+    ///
+    /// let mut bomb = Bomb::new(BombType::FreeFall);
+    /// bomb.set_drag(0.3);
+    /// assert_eq!(bomb.get_drag(), 0.3);
+    /// ```
+    pub fn set_drag(&mut self, drag: f32) {
+        self.drag = drag;
+    }
+
+    /// Get the depth of concrete in meters the bomb can go through
+    pub fn get_concrete_penetration(&self) -> f32 {
+        self.concrete_penetration
+    }
+
+    /// Define a new depth of concrete in meters the bomb can go through
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use weapons::bombs::{Bomb, BombType};
+    ///
+    /// let mut bomb = Bomb::new(BombType::BunkerBuster);
+    /// bomb.set_concrete_penetration(6.0);
+    /// assert_eq!(bomb.get_concrete_penetration(), 6.0);
+    /// ```
+    pub fn set_concrete_penetration(&mut self, concrete_penetration: f32) {
+        self.concrete_penetration = concrete_penetration;
+    }
+
+    /// Get the information on the bomb
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use weapons::bombs::{Bomb, BombType};
+    /// use weapons::WeaponInformations;
+    ///
+    /// let bomb = Bomb::new(BombType::FreeFall);
+    /// assert_eq!(bomb.get_informations(), &WeaponInformations::default());
+    /// ```
+    pub fn get_informations(&self) -> &WeaponInformations {
+        &self.informations
+    }
+
+    /// Get the information on the bomb with a mutable reference
+    ///
+    /// See Self::get_informations
+    pub fn get_informations_mut(&mut self) -> &mut WeaponInformations {
+        &mut self.informations
+    }
+
+    /// Get the damages given by the bomb
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use weapons::bombs::{Bomb, BombType};
+    /// use weapons::Damages;
+    ///
+    /// let bomb = Bomb::new(BombType::FreeFall);
+    /// assert_eq!(bomb.get_damages(), &Damages::default());
+    /// ```
+    pub fn get_damages(&self) -> &Damages {
+        &self.damages
+    }
+
+    /// Get the damages given by the bomb with a mutable reference
+    ///
+    /// See Self::get_damages
+    pub fn get_damages_mut(&mut self) -> &mut Damages {
+        &mut self.damages
+    }
+}
+
+impl Weapon for Bomb {
+    fn category(&self) -> WeaponCategory {
+        WeaponCategory::Bomb
+    }
+
+    fn get_informations(&self) -> &WeaponInformations {
+        &self.informations
+    }
+
+    fn get_informations_mut(&mut self) -> &mut WeaponInformations {
+        &mut self.informations
+    }
+
+    fn get_damages(&self) -> &Damages {
+        &self.damages
+    }
+
+    fn get_damages_mut(&mut self) -> &mut Damages {
+        &mut self.damages
+    }
+}
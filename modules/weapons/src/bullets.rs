@@ -1,6 +1,6 @@
 //! This module is used to define bullets
 
-use crate::{Damages, WeaponInformations};
+use crate::{Damages, Weapon, WeaponCategory, WeaponInformations};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Serialize, Deserialize)]
@@ -50,6 +50,7 @@ impl TryFrom<i64> for BulletType {
 /// Implement a bullet
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Bullet {
+    #[serde(deserialize_with = "crate::deserialize_id")]
     bullet_type: BulletType,
 
     informations: WeaponInformations,
@@ -150,3 +151,25 @@ impl Bullet {
         &mut self.damages
     }
 }
+
+impl Weapon for Bullet {
+    fn category(&self) -> WeaponCategory {
+        WeaponCategory::Bullet
+    }
+
+    fn get_informations(&self) -> &WeaponInformations {
+        &self.informations
+    }
+
+    fn get_informations_mut(&mut self) -> &mut WeaponInformations {
+        &mut self.informations
+    }
+
+    fn get_damages(&self) -> &Damages {
+        &self.damages
+    }
+
+    fn get_damages_mut(&mut self) -> &mut Damages {
+        &mut self.damages
+    }
+}
@@ -0,0 +1,404 @@
+//! This module resolve the hits of weapons on their targets
+//!
+//! Armor is expressed in millimeters of rolled homogeneous armor. A weapon penetrating the armor
+//! deals its full damages, otherwise only the part of the damages going through the armor (blast,
+//! spalling...) is dealt.
+//!
+//! A worn weapon may fail, so its damages are reduced by the probability that it fails, see
+//! [Condition::failure_probability].
+
+use crate::bombs::{Bomb, BombType};
+use crate::bullets::BulletType;
+use crate::condition::Condition;
+use crate::damage_type::Vulnerability;
+use crate::missiles::WarheadCharge;
+use crate::shells::ShellType;
+use crate::{TargetKind, WeaponRef};
+use serde::{Deserialize, Serialize};
+
+/// The side of a target facing the weapon
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[repr(u8)]
+pub enum Facing {
+    #[default]
+    Front = 0,
+    Side = 1,
+    Rear = 2,
+    Top = 3,
+}
+
+impl TryFrom<i64> for Facing {
+    type Error = ();
+
+    fn try_from(value: i64) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Self::Front),
+            1 => Ok(Self::Side),
+            2 => Ok(Self::Rear),
+            3 => Ok(Self::Top),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Define the armor of a target on each side, in millimeters
+#[derive(Clone, Default, Debug, Serialize, Deserialize, PartialEq, PartialOrd, Copy)]
+pub struct Armor {
+    #[serde(default)]
+    pub front: f32,
+    #[serde(default)]
+    pub side: f32,
+    #[serde(default)]
+    pub rear: f32,
+    #[serde(default)]
+    pub top: f32,
+}
+
+impl Armor {
+    /// Get the armor of a side
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use weapons::combat::{Armor, Facing};
+    ///
+    /// let armor = Armor {
+    ///     front: 600.0,
+    ///     side: 80.0,
+    ///     ..Default::default()
+    /// };
+    /// assert_eq!(armor.against(Facing::Front), 600.0);
+    /// assert_eq!(armor.against(Facing::Top), 0.0);
+    /// ```
+    pub fn against(&self, facing: Facing) -> f32 {
+        let armor = match facing {
+            Facing::Front => self.front,
+            Facing::Side => self.side,
+            Facing::Rear => self.rear,
+            Facing::Top => self.top,
+        };
+        armor.max(0.0)
+    }
+}
+
+/// Millimeters of rolled homogeneous armor equivalent to one meter of concrete
+pub const CONCRETE_TO_ARMOR: f32 = 200.0;
+
+/// A target hit by a weapon
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Copy)]
+pub struct TargetProfile {
+    #[serde(deserialize_with = "crate::deserialize_id")]
+    pub kind: TargetKind,
+    #[serde(default)]
+    pub armor: Armor,
+    /// The side of the target facing the weapon
+    #[serde(default)]
+    pub facing: Facing,
+    /// How the target reacts to each damage type
+    #[serde(default)]
+    pub vulnerability: Vulnerability,
+}
+
+impl TargetProfile {
+    /// Create an unarmored target facing the weapon
+    pub fn new(kind: TargetKind) -> Self {
+        Self {
+            kind,
+            armor: Armor::default(),
+            facing: Facing::Front,
+            vulnerability: Vulnerability::default(),
+        }
+    }
+}
+
+/// How the type of ammunition changes the effect of a hit
+#[derive(Clone, Debug, PartialEq, PartialOrd, Copy)]
+pub struct HitModifier {
+    /// Multiplier applied to the penetration of the weapon
+    pub penetration: f32,
+    /// Multiplier applied to the damages of the weapon
+    pub damages: f32,
+    /// Part of the damages still dealt when the armor isn't penetrated
+    pub blocked: f32,
+}
+
+impl Default for HitModifier {
+    fn default() -> Self {
+        Self {
+            penetration: 1.0,
+            damages: 1.0,
+            blocked: 0.0,
+        }
+    }
+}
+
+impl HitModifier {
+    /// Get the modifier of a weapon, depending on its ammunition or warhead
+    ///
+    /// Explosive weapons (missiles, torpedoes, bombs and drones) still deal part of their damages
+    /// to an armored target they don't penetrate. Firearms don't have any modifier.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use weapons::combat::HitModifier;
+    /// use weapons::shells::{Shell, ShellType};
+    /// use weapons::WeaponRef;
+    ///
+    /// let apfsds = Shell::new(ShellType::ArmorPiercingFinStabilizedDiscardingSabot);
+    /// let he = Shell::new(ShellType::HighExplosive);
+    /// assert!(
+    ///     HitModifier::of(WeaponRef::Shell(&apfsds)).penetration
+    ///         > HitModifier::of(WeaponRef::Shell(&he)).penetration
+    /// );
+    /// ```
+    pub fn of(weapon: WeaponRef<'_>) -> Self {
+        let (penetration, damages, blocked) = match weapon {
+            WeaponRef::Shell(shell) => match shell.get_shell_type() {
+                ShellType::ArmorPiercing => (1.0, 1.0, 0.0),
+                ShellType::HighExplosive => (0.2, 1.0, 0.3),
+                ShellType::Fragmentation => (0.1, 1.0, 0.1),
+                ShellType::HighExplosiveAntiTank => (1.0, 1.0, 0.1),
+                ShellType::ArmorPiecingDiscardingSabot => (1.3, 1.0, 0.0),
+                ShellType::ArmorPiercingFinStabilizedDiscardingSabot => (1.6, 1.0, 0.0),
+                ShellType::TandemCharge => (1.5, 1.0, 0.1),
+                ShellType::Mortar => (0.1, 1.0, 0.3),
+            },
+            WeaponRef::Bullet(bullet) => match bullet.get_bullet_type() {
+                BulletType::Ordinary => (1.0, 1.0, 0.0),
+                BulletType::Expansible => (0.5, 1.3, 0.0),
+                BulletType::ArmorPiercing => (2.0, 1.0, 0.0),
+                BulletType::Frangible => (0.3, 1.1, 0.0),
+                BulletType::Tracing => (1.0, 0.9, 0.0),
+                BulletType::ArmorPiercingIncendiary => (2.0, 1.1, 0.0),
+                BulletType::SabotedLightArmorPenetrator => (2.5, 1.0, 0.0),
+                BulletType::Fragmentation => (0.5, 1.2, 0.0),
+            },
+            WeaponRef::Missile(missile) => match missile.get_warhead_charge() {
+                WarheadCharge::Standard => (1.0, 1.0, 0.5),
+                WarheadCharge::Chemical => (1.0, 1.0, 0.2),
+                WarheadCharge::Nuclear => (1.0, 1.0, 1.0),
+                WarheadCharge::Biological => (1.0, 1.0, 0.2),
+            },
+            WeaponRef::Torpedo(_) => (1.0, 1.0, 0.8),
+            WeaponRef::Bomb(bomb) => match bomb.get_bomb_type() {
+                BombType::FreeFall => (1.0, 1.0, 0.5),
+                BombType::Guided => (1.0, 1.0, 0.5),
+                BombType::Cluster => (1.0, 1.0, 0.3),
+                BombType::BunkerBuster => (1.0, 1.0, 0.5),
+            },
+            WeaponRef::Drone(_) => (1.0, 1.0, 0.4),
+            WeaponRef::FireArm(_) => return Self::default(),
+        };
+        Self {
+            penetration,
+            damages,
+            blocked,
+        }
+    }
+}
+
+/// The result of a hit
+#[derive(Clone, Default, Debug, Serialize, Deserialize, PartialEq, PartialOrd, Copy)]
+pub struct DamageReport {
+    /// If the weapon went through the armor
+    pub penetrated: bool,
+    /// The armor of the side that was hit, in millimeters
+    pub armor: f32,
+    /// The penetration of the weapon after modifiers, in millimeters
+    pub penetration: f32,
+    /// The probability (from 0.0 to 1.0) that the weapon worked, depending on its condition
+    pub reliability: f32,
+    /// The damages dealt to the target, reduced by the reliability
+    pub damages: f32,
+}
+
+/// Get the penetration of a weapon in millimeters, before modifiers
+///
+/// The penetration of a bomb in concrete is converted with [CONCRETE_TO_ARMOR], and the best of
+/// the two penetrations is used.
+fn base_penetration(weapon: WeaponRef<'_>) -> f32 {
+    let penetration = weapon.weapon().get_informations().penetration;
+    let penetration = match weapon {
+        WeaponRef::Bomb(bomb) => penetration.max(concrete_penetration(bomb)),
+        _ => penetration,
+    };
+    penetration.max(0.0)
+}
+
+/// Get the penetration of a bomb in concrete, in millimeters of armor
+fn concrete_penetration(bomb: &Bomb) -> f32 {
+    bomb.get_concrete_penetration() * CONCRETE_TO_ARMOR
+}
+
+/// Resolve the hit of a weapon in a given condition on a target
+///
+/// # Example
+///
+/// ```
+/// use weapons::combat::{resolve_hit, Armor, Facing, TargetProfile};
+/// use weapons::condition::Condition;
+/// use weapons::shells::{Shell, ShellType};
+/// use weapons::{TargetKind, WeaponRef};
+///
+/// let mut shell = Shell::new(ShellType::ArmorPiercing);
+/// shell.get_informations_mut().penetration = 500.0;
+/// shell.get_damages_mut().tank = 8.0;
+///
+/// let mut tank = TargetProfile::new(TargetKind::Tank);
+/// tank.armor = Armor {
+///     front: 700.0,
+///     side: 100.0,
+///     ..Default::default()
+/// };
+///
+/// let report = resolve_hit(WeaponRef::Shell(&shell), &tank, Condition::default());
+/// assert!(!report.penetrated);
+/// assert_eq!(report.damages, 0.0);
+///
+/// tank.facing = Facing::Side;
+/// let report = resolve_hit(WeaponRef::Shell(&shell), &tank, Condition::default());
+/// assert!(report.penetrated);
+/// assert_eq!(report.damages, 8.0);
+///
+/// // a worn shell may not explode
+/// let report = resolve_hit(WeaponRef::Shell(&shell), &tank, Condition::new(0.5));
+/// assert_eq!(report.reliability, 0.75);
+/// assert_eq!(report.damages, 6.0);
+/// ```
+pub fn resolve_hit(
+    weapon: WeaponRef<'_>,
+    target: &TargetProfile,
+    condition: Condition,
+) -> DamageReport {
+    let modifier = HitModifier::of(weapon);
+    let armor = target.armor.against(target.facing);
+    let penetration = base_penetration(weapon) * modifier.penetration;
+    let penetrated = penetration >= armor;
+    let reliability = 1.0 - condition.failure_probability();
+
+    let mut damages = weapon
+        .weapon()
+        .get_damages()
+        .effective_against(target.kind, &target.vulnerability)
+        * modifier.damages;
+    if !penetrated {
+        damages *= modifier.blocked;
+    }
+    damages *= reliability;
+
+    DamageReport {
+        penetrated,
+        armor,
+        penetration,
+        reliability,
+        damages,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    #[test]
+    fn test_unarmored_targets_take_full_damages() {
+        use super::*;
+        use crate::bullets::Bullet;
+
+        let mut bullet = Bullet::new(BulletType::Expansible);
+        bullet.get_damages_mut().infantry = 1.0;
+
+        let report = resolve_hit(
+            WeaponRef::Bullet(&bullet),
+            &TargetProfile::new(TargetKind::Infantry),
+            Condition::default(),
+        );
+        assert!(report.penetrated);
+        assert_eq!(report.damages, 1.3);
+    }
+
+    #[test]
+    fn test_blast_goes_through_armor() {
+        use super::*;
+        use crate::shells::Shell;
+
+        let mut shell = Shell::new(ShellType::HighExplosive);
+        shell.get_informations_mut().penetration = 100.0;
+        shell.get_damages_mut().armored_vehicle = 10.0;
+
+        let mut target = TargetProfile::new(TargetKind::ArmoredVehicle);
+        target.armor.front = 50.0;
+        let report = resolve_hit(WeaponRef::Shell(&shell), &target, Condition::default());
+        assert!(!report.penetrated);
+        assert_eq!(report.penetration, 20.0);
+        assert_eq!(report.damages, 3.0);
+    }
+
+    #[test]
+    fn test_explosives_damage_armored_targets() {
+        use super::*;
+        use crate::missiles::{Missile, MissileGuidanceType, ProjectileType};
+
+        let mut missile = Missile::new(MissileGuidanceType::Gps, ProjectileType::Ballistic);
+        missile.get_damages_mut().tank = 90.0;
+
+        let mut tank = TargetProfile::new(TargetKind::Tank);
+        tank.armor.front = 1.0;
+
+        let report = resolve_hit(WeaponRef::Missile(&missile), &tank, Condition::default());
+        assert!(!report.penetrated);
+        assert_eq!(report.damages, 45.0);
+
+        missile.set_warhead_charge(WarheadCharge::Nuclear);
+        let report = resolve_hit(WeaponRef::Missile(&missile), &tank, Condition::default());
+        assert_eq!(report.damages, 90.0);
+    }
+
+    #[test]
+    fn test_bombs_penetrate_concrete() {
+        use super::*;
+
+        let mut bomb = Bomb::new(BombType::BunkerBuster);
+        bomb.set_concrete_penetration(6.0);
+        bomb.get_damages_mut().building = 10.0;
+
+        let mut bunker = TargetProfile::new(TargetKind::Building);
+        bunker.armor.top = 1000.0;
+        bunker.facing = Facing::Top;
+
+        let report = resolve_hit(WeaponRef::Bomb(&bomb), &bunker, Condition::default());
+        assert!(report.penetrated);
+        assert_eq!(report.penetration, 1200.0);
+        assert_eq!(report.damages, 10.0);
+
+        bomb.set_concrete_penetration(1.0);
+        let report = resolve_hit(WeaponRef::Bomb(&bomb), &bunker, Condition::default());
+        assert!(!report.penetrated);
+        assert_eq!(report.damages, 5.0);
+    }
+
+    #[test]
+    fn test_broken_weapons_deal_no_damages() {
+        use super::*;
+        use crate::bullets::Bullet;
+
+        let mut bullet = Bullet::new(BulletType::Ordinary);
+        bullet.get_damages_mut().infantry = 1.0;
+        let infantry = TargetProfile::new(TargetKind::Infantry);
+
+        let report = resolve_hit(WeaponRef::Bullet(&bullet), &infantry, Condition::new(0.0));
+        assert!(report.penetrated);
+        assert_eq!(report.reliability, 0.0);
+        assert_eq!(report.damages, 0.0);
+    }
+
+    #[test]
+    fn test_firearms_have_no_modifier() {
+        use super::*;
+        use crate::firearm::{FireArm, FireArmType};
+
+        let famas = FireArm::new(FireArmType::Assault, "5.56");
+        assert_eq!(
+            HitModifier::of(WeaponRef::FireArm(&famas)),
+            HitModifier::default()
+        );
+    }
+}
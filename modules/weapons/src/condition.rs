@@ -0,0 +1,267 @@
+//! This module define the condition of weapons
+//!
+//! A weapon that isn't maintained slowly degrades, and a degraded weapon is more likely to fail
+//! when it's used.
+
+use resources::Money;
+use serde::{Deserialize, Serialize};
+
+/// The condition of a brand new weapon
+pub const MAX_CONDITION: f32 = 1.0;
+
+/// The condition of a weapon, from 0.0 (broken) to 1.0 (brand new)
+///
+/// # Example
+///
+/// ```
+/// use weapons::condition::Condition;
+///
+/// let mut condition = Condition::default();
+/// condition.degrade(0.25);
+/// assert_eq!(condition.get(), 0.75);
+/// ```
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, PartialOrd)]
+#[serde(from = "f32")]
+pub struct Condition(f32);
+
+impl Condition {
+    /// Create a new condition, the value is clamped between 0.0 and 1.0
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use weapons::condition::Condition;
+    ///
+    /// assert_eq!(Condition::new(0.5).get(), 0.5);
+    /// assert_eq!(Condition::new(2.0).get(), 1.0);
+    /// ```
+    pub fn new(value: f32) -> Self {
+        Self(value.clamp(0.0, MAX_CONDITION))
+    }
+
+    /// Get the value of the condition
+    pub fn get(&self) -> f32 {
+        self.0
+    }
+
+    /// Return true if the weapon can't be used anymore
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use weapons::condition::Condition;
+    ///
+    /// assert!(Condition::new(0.0).is_broken());
+    /// assert!(!Condition::default().is_broken());
+    /// ```
+    pub fn is_broken(&self) -> bool {
+        self.0 <= 0.0
+    }
+
+    /// Degrade the condition, it can't go under 0.0
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use weapons::condition::Condition;
+    ///
+    /// let mut condition = Condition::new(0.5);
+    /// condition.degrade(1.0);
+    /// assert_eq!(condition.get(), 0.0);
+    /// ```
+    pub fn degrade(&mut self, amount: f32) {
+        self.0 = (self.0 - amount).max(0.0);
+    }
+
+    /// Repair the weapon, the condition can't go over 1.0
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use weapons::condition::Condition;
+    ///
+    /// let mut condition = Condition::new(0.5);
+    /// condition.repair(1.0);
+    /// assert_eq!(condition.get(), 1.0);
+    /// ```
+    pub fn repair(&mut self, amount: f32) {
+        self.0 = (self.0 + amount).min(MAX_CONDITION);
+    }
+
+    /// Get the probability (from 0.0 to 1.0) that the weapon fails when used
+    ///
+    /// A brand new weapon never fails, and the probability rises faster as the weapon degrades.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use weapons::condition::Condition;
+    ///
+    /// assert_eq!(Condition::default().failure_probability(), 0.0);
+    /// assert_eq!(Condition::new(0.5).failure_probability(), 0.25);
+    /// assert_eq!(Condition::new(0.0).failure_probability(), 1.0);
+    /// ```
+    pub fn failure_probability(&self) -> f32 {
+        (MAX_CONDITION - self.0).powi(2)
+    }
+}
+
+impl Default for Condition {
+    fn default() -> Self {
+        Self(MAX_CONDITION)
+    }
+}
+
+impl From<f32> for Condition {
+    fn from(value: f32) -> Self {
+        Self::new(value)
+    }
+}
+
+/// Define how much it costs to keep a weapon in a good condition
+#[derive(Clone, Default, Debug, Serialize, Deserialize, PartialEq, PartialOrd)]
+pub struct Maintenance {
+    /// The amount of money needed to maintain one unit of the weapon for one tick
+    #[serde(default)]
+    pub cost: Money,
+    /// The condition lost by one unit of the weapon for each tick without maintenance
+    #[serde(default)]
+    pub wear: f32,
+}
+
+impl Maintenance {
+    /// Get the amount of money needed to maintain a quantity of weapons for one tick
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use resources::Money;
+    /// use weapons::condition::Maintenance;
+    ///
+    /// let maintenance = Maintenance {
+    ///     cost: Money::new(10),
+    ///     wear: 0.01,
+    /// };
+    /// assert_eq!(maintenance.cost_for(5), Money::new(50));
+    /// ```
+    pub fn cost_for(&self, quantity: u64) -> Money {
+        let quantity = i64::try_from(quantity).unwrap_or(i64::MAX);
+        Money::new(self.cost.get().saturating_mul(quantity))
+    }
+
+    /// Apply one tick of maintenance to the condition of a quantity of weapons
+    ///
+    /// The condition is degraded proportionally to the part of the maintenance cost that wasn't
+    /// paid. If everything was paid, the condition stays the same.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use resources::Money;
+    /// use weapons::condition::{Condition, Maintenance};
+    ///
+    /// let maintenance = Maintenance {
+    ///     cost: Money::new(10),
+    ///     wear: 0.1,
+    /// };
+    /// let mut condition = Condition::default();
+    ///
+    /// maintenance.apply(&mut condition, &Money::new(20), 2);
+    /// assert_eq!(condition.get(), 1.0);
+    ///
+    /// maintenance.apply(&mut condition, &Money::new(10), 2);
+    /// assert_eq!(condition.get(), 0.95);
+    /// ```
+    pub fn apply(&self, condition: &mut Condition, spent: &Money, quantity: u64) {
+        let required = self.cost_for(quantity).get();
+        let paid = if required <= 0 {
+            1.0
+        } else {
+            (spent.get() as f32 / required as f32).clamp(0.0, 1.0)
+        };
+
+        condition.degrade(self.wear * (1.0 - paid));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    #[test]
+    fn test_condition_default() {
+        use super::*;
+
+        let condition = Condition::default();
+        assert_eq!(condition.get(), MAX_CONDITION);
+        assert!(!condition.is_broken());
+        assert_eq!(condition.failure_probability(), 0.0);
+    }
+
+    #[test]
+    fn test_condition_bounds() {
+        use super::*;
+
+        let mut condition = Condition::new(-1.0);
+        assert_eq!(condition.get(), 0.0);
+        assert!(condition.is_broken());
+
+        condition.repair(5.0);
+        assert_eq!(condition.get(), MAX_CONDITION);
+    }
+
+    #[test]
+    fn test_maintenance_without_spending() {
+        use super::*;
+
+        let maintenance = Maintenance {
+            cost: Money::new(5),
+            wear: 0.25,
+        };
+        let mut condition = Condition::default();
+
+        let mut previous = condition.failure_probability();
+        for _ in 0..4 {
+            maintenance.apply(&mut condition, &Money::new(0), 1);
+            assert!(condition.failure_probability() > previous);
+            previous = condition.failure_probability();
+        }
+        assert!(condition.is_broken());
+    }
+
+    #[test]
+    fn test_maintenance_cost_saturates() {
+        use super::*;
+
+        let maintenance = Maintenance {
+            cost: Money::new(10),
+            wear: 0.1,
+        };
+        assert_eq!(maintenance.cost_for(u64::MAX), Money::new(i64::MAX));
+    }
+
+    #[test]
+    fn test_maintenance_free() {
+        use super::*;
+
+        let maintenance = Maintenance {
+            cost: Money::new(0),
+            wear: 0.2,
+        };
+        let mut condition = Condition::default();
+
+        maintenance.apply(&mut condition, &Money::new(0), 10);
+        assert_eq!(condition.get(), MAX_CONDITION);
+    }
+
+    #[cfg(feature = "config")]
+    #[test]
+    fn test_condition_deserialize_is_clamped() {
+        use super::*;
+
+        let condition: Condition = serde_json::from_str("5.0").unwrap();
+        assert_eq!(condition.get(), MAX_CONDITION);
+        assert_eq!(condition.failure_probability(), 0.0);
+
+        let condition: Condition = serde_json::from_str("-1.0").unwrap();
+        assert!(condition.is_broken());
+    }
+}
@@ -0,0 +1,99 @@
+//! This module define the resources needed to produce weapons
+
+use resources::{Money, Ores, RefinedProduct, WorkForce};
+use serde::{Deserialize, Serialize};
+
+/// Define the resources needed to produce one unit of a weapon
+#[derive(Clone, Default, Debug, Serialize, Deserialize, PartialEq, PartialOrd)]
+pub struct Cost {
+    #[serde(default)]
+    pub money: Money,
+    #[serde(default)]
+    pub ores: Ores,
+    #[serde(default)]
+    pub refined_products: RefinedProduct,
+    #[serde(default)]
+    pub work_force: WorkForce,
+}
+
+impl Cost {
+    /// Get the resources needed to produce a quantity of weapons
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use resources::{Money, Ores, RefinedProduct};
+    /// use weapons::cost::Cost;
+    ///
+    /// let cost = Cost {
+    ///     money: Money::new(100),
+    ///     ores: Ores::new(0, 2),
+    ///     refined_products: RefinedProduct::new(1, 3, 0),
+    ///     ..Default::default()
+    /// };
+    /// let total = cost.cost_for(4);
+    /// assert_eq!(total.money.get(), 400);
+    /// assert_eq!(total.ores.get_rate_metals(), 8);
+    /// assert_eq!(total.refined_products.get_chips(), 12);
+    /// assert_eq!(total.work_force.get(), 0);
+    /// ```
+    pub fn cost_for(&self, quantity: u64) -> Cost {
+        let signed_quantity = i64::try_from(quantity).unwrap_or(i64::MAX);
+        Cost {
+            money: Money::new(self.money.get().saturating_mul(signed_quantity)),
+            ores: Ores::new(
+                self.ores.get_uranium().saturating_mul(quantity),
+                self.ores.get_rate_metals().saturating_mul(quantity),
+            ),
+            refined_products: RefinedProduct::new(
+                self.refined_products.get_alloys().saturating_mul(quantity),
+                self.refined_products.get_chips().saturating_mul(quantity),
+                self.refined_products
+                    .get_components()
+                    .saturating_mul(quantity),
+            ),
+            work_force: WorkForce::new(self.work_force.get().saturating_mul(quantity)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    #[test]
+    fn test_cost_saturates() {
+        use super::*;
+
+        let cost = Cost {
+            money: Money::new(10),
+            work_force: WorkForce::new(2),
+            ..Default::default()
+        };
+        let total = cost.cost_for(u64::MAX);
+        assert_eq!(total.money.get(), i64::MAX);
+        assert_eq!(total.work_force.get(), u64::MAX);
+    }
+
+    #[cfg(feature = "config")]
+    #[test]
+    fn test_deserialize() {
+        use super::*;
+
+        let cost: Cost = toml::from_str(
+            r#"
+            money = 2500
+            work_force = 12
+
+            [ores]
+            uranium = 3
+
+            [refined_products]
+            chips = 40
+            "#,
+        )
+        .unwrap();
+        assert_eq!(cost.money, Money::new(2500));
+        assert_eq!(cost.work_force, WorkForce::new(12));
+        assert_eq!(cost.ores, Ores::new(3, 0));
+        assert_eq!(cost.refined_products, RefinedProduct::new(0, 40, 0));
+    }
+}
@@ -0,0 +1,488 @@
+//! This module define point-defense and countermeasure systems, used to stop incoming weapons
+//! before they reach their target
+
+use crate::missiles::{Missile, MissileGuidanceType};
+use crate::{WeaponCategory, WeaponInformations};
+use serde::{Deserialize, Serialize};
+
+/// Enumeration representing the different types of countermeasures.
+#[derive(Clone, Debug, PartialEq, PartialOrd, Copy, Serialize, Deserialize)]
+#[repr(u8)]
+pub enum CountermeasureType {
+    /// Close-in weapon system, a fast firing gun destroying incoming weapons at short range.
+    Ciws = 0,
+    /// Hot decoys attracting heat-seeking missiles.
+    Flares = 1,
+    /// Cloud of metallic strips blinding radars.
+    Chaff = 2,
+    /// Electronic countermeasure jamming radars and radio links.
+    Jammer = 3,
+    /// Missile destroying the incoming weapon in flight.
+    Interceptor = 4,
+}
+
+impl TryFrom<i64> for CountermeasureType {
+    type Error = ();
+
+    fn try_from(value: i64) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Self::Ciws),
+            1 => Ok(Self::Flares),
+            2 => Ok(Self::Chaff),
+            3 => Ok(Self::Jammer),
+            4 => Ok(Self::Interceptor),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Define the probability (from 0.0 to 1.0) that a countermeasure stops an incoming weapon
+#[derive(Clone, Default, Debug, Serialize, Deserialize, PartialEq, PartialOrd, Copy)]
+pub struct Interception {
+    /// The probability to stop a missile
+    #[serde(default)]
+    pub missile: f32,
+    /// The probability to stop a torpedo
+    #[serde(default)]
+    pub torpedo: f32,
+    /// The probability to stop a shell
+    #[serde(default)]
+    pub shell: f32,
+    /// The probability to stop a bullet
+    #[serde(default)]
+    pub bullet: f32,
+    /// The probability to stop a bomb
+    #[serde(default)]
+    pub bomb: f32,
+    /// The probability to stop a drone
+    #[serde(default)]
+    pub drone: f32,
+}
+
+impl Interception {
+    /// Get the probability to stop a weapon of a category, clamped between 0.0 and 1.0
+    ///
+    /// Firearms are never intercepted themselves, only the bullets they fire.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use weapons::countermeasures::Interception;
+    /// use weapons::WeaponCategory;
+    ///
+    /// let interception = Interception {
+    ///     missile: 0.6,
+    ///     ..Default::default()
+    /// };
+    /// assert_eq!(interception.against(WeaponCategory::Missile), 0.6);
+    /// assert_eq!(interception.against(WeaponCategory::Torpedo), 0.0);
+    /// ```
+    pub fn against(&self, category: WeaponCategory) -> f32 {
+        let probability = match category {
+            WeaponCategory::Missile => self.missile,
+            WeaponCategory::Torpedo => self.torpedo,
+            WeaponCategory::Shell => self.shell,
+            WeaponCategory::FireArm => 0.0,
+            WeaponCategory::Bullet => self.bullet,
+            WeaponCategory::Bomb => self.bomb,
+            WeaponCategory::Drone => self.drone,
+        };
+        probability.clamp(0.0, 1.0)
+    }
+}
+
+/// Define how much (from 0.0 to 1.0) a countermeasure affects missiles of each guidance type
+///
+/// It multiplies the probability to intercept a missile. The defaults of the countermeasure type
+/// are given by [Susceptibility::of], and each value can be overridden by a countermeasure.
+#[derive(Clone, Default, Debug, Serialize, Deserialize, PartialEq, PartialOrd, Copy)]
+pub struct Susceptibility {
+    #[serde(default)]
+    pub laser: f32,
+    #[serde(default)]
+    pub radar: f32,
+    #[serde(default)]
+    pub heat: f32,
+    #[serde(default)]
+    pub gps: f32,
+    #[serde(default)]
+    pub radio: f32,
+}
+
+impl Susceptibility {
+    /// Get the default susceptibility of the missiles to a type of countermeasure
+    ///
+    /// Guns and interceptors destroy the missile whatever its guidance, while decoys and
+    /// jammers only fool the guidance they were designed against.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use weapons::countermeasures::{CountermeasureType, Susceptibility};
+    /// use weapons::missiles::MissileGuidanceType;
+    ///
+    /// let flares = Susceptibility::of(CountermeasureType::Flares);
+    /// assert_eq!(flares.against(MissileGuidanceType::Heat), 0.9);
+    /// assert_eq!(flares.against(MissileGuidanceType::Radar), 0.0);
+    /// ```
+    pub fn of(countermeasure_type: CountermeasureType) -> Self {
+        let (laser, radar, heat, gps, radio) = match countermeasure_type {
+            CountermeasureType::Ciws => (1.0, 1.0, 1.0, 1.0, 1.0),
+            CountermeasureType::Flares => (0.0, 0.0, 0.9, 0.0, 0.0),
+            CountermeasureType::Chaff => (0.1, 0.9, 0.0, 0.0, 0.0),
+            CountermeasureType::Jammer => (0.0, 0.7, 0.0, 0.5, 1.0),
+            CountermeasureType::Interceptor => (1.0, 1.0, 1.0, 1.0, 1.0),
+        };
+        Self {
+            laser,
+            radar,
+            heat,
+            gps,
+            radio,
+        }
+    }
+
+    /// Get the susceptibility of a guidance type, clamped between 0.0 and 1.0
+    pub fn against(&self, guidance: MissileGuidanceType) -> f32 {
+        let susceptibility = match guidance {
+            MissileGuidanceType::Laser => self.laser,
+            MissileGuidanceType::Radar => self.radar,
+            MissileGuidanceType::Heat => self.heat,
+            MissileGuidanceType::Gps => self.gps,
+            MissileGuidanceType::Radio => self.radio,
+        };
+        susceptibility.clamp(0.0, 1.0)
+    }
+}
+
+/// The susceptibility values overridden by a countermeasure
+///
+/// Missing values are taken from the defaults of the countermeasure type.
+#[derive(Clone, Default, Debug, Serialize, Deserialize, PartialEq, PartialOrd, Copy)]
+pub struct SusceptibilityOverride {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub laser: Option<f32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub radar: Option<f32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub heat: Option<f32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gps: Option<f32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub radio: Option<f32>,
+}
+
+impl SusceptibilityOverride {
+    /// Replace the default values by the overridden ones
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use weapons::countermeasures::{CountermeasureType, Susceptibility, SusceptibilityOverride};
+    ///
+    /// let heat_only = SusceptibilityOverride {
+    ///     heat: Some(0.5),
+    ///     ..Default::default()
+    /// };
+    /// let ciws = heat_only.apply(Susceptibility::of(CountermeasureType::Ciws));
+    /// assert_eq!(ciws.heat, 0.5);
+    /// assert_eq!(ciws.radar, 1.0);
+    /// ```
+    pub fn apply(&self, defaults: Susceptibility) -> Susceptibility {
+        Susceptibility {
+            laser: self.laser.unwrap_or(defaults.laser),
+            radar: self.radar.unwrap_or(defaults.radar),
+            heat: self.heat.unwrap_or(defaults.heat),
+            gps: self.gps.unwrap_or(defaults.gps),
+            radio: self.radio.unwrap_or(defaults.radio),
+        }
+    }
+}
+
+impl From<Susceptibility> for SusceptibilityOverride {
+    fn from(value: Susceptibility) -> Self {
+        Self {
+            laser: Some(value.laser),
+            radar: Some(value.radar),
+            heat: Some(value.heat),
+            gps: Some(value.gps),
+            radio: Some(value.radio),
+        }
+    }
+}
+
+/// A countermeasure system
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Countermeasure {
+    #[serde(deserialize_with = "crate::deserialize_id")]
+    countermeasure_type: CountermeasureType,
+    #[serde(default)]
+    interception: Interception,
+    /// Overrides some values of the default susceptibility of the countermeasure type
+    #[serde(default)]
+    susceptibility: SusceptibilityOverride,
+
+    /// The range is the distance in kilometers the system covers
+    informations: WeaponInformations,
+}
+
+impl Countermeasure {
+    /// Create a new countermeasure that doesn't intercept anything yet
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use weapons::countermeasures::{Countermeasure, CountermeasureType, Interception};
+    ///
+    /// let ciws = Countermeasure::new(CountermeasureType::Ciws);
+    /// assert_eq!(ciws.get_countermeasure_type(), CountermeasureType::Ciws);
+    /// assert_eq!(ciws.get_interception(), &Interception::default());
+    /// ```
+    pub fn new(countermeasure_type: CountermeasureType) -> Self {
+        Self {
+            countermeasure_type,
+            interception: Interception::default(),
+            susceptibility: SusceptibilityOverride::default(),
+            informations: WeaponInformations::default(),
+        }
+    }
+
+    /// Get the type of the countermeasure
+    pub fn get_countermeasure_type(&self) -> CountermeasureType {
+        self.countermeasure_type
+    }
+
+    /// Define a new type for the countermeasure
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use weapons::countermeasures::{Countermeasure, CountermeasureType};
+    ///
+    /// let mut countermeasure = Countermeasure::new(CountermeasureType::Flares);
+    /// countermeasure.set_countermeasure_type(CountermeasureType::Chaff);
+    /// assert_eq!(countermeasure.get_countermeasure_type(), CountermeasureType::Chaff);
+    /// ```
+    pub fn set_countermeasure_type(&mut self, countermeasure_type: CountermeasureType) {
+        self.countermeasure_type = countermeasure_type;
+    }
+
+    /// Get the interception probabilities of the countermeasure
+    pub fn get_interception(&self) -> &Interception {
+        &self.interception
+    }
+
+    /// Get the interception probabilities of the countermeasure with a mutable reference
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use weapons::countermeasures::{Countermeasure, CountermeasureType};
+    /// use weapons::WeaponCategory;
+    ///
+    /// let mut ciws = Countermeasure::new(CountermeasureType::Ciws);
+    /// ciws.get_interception_mut().missile = 0.4;
+    /// assert_eq!(ciws.interception_probability(WeaponCategory::Missile), 0.4);
+    /// ```
+    pub fn get_interception_mut(&mut self) -> &mut Interception {
+        &mut self.interception
+    }
+
+    /// Set the interception probabilities of the countermeasure
+    pub fn set_interception(&mut self, interception: Interception) {
+        self.interception = interception;
+    }
+
+    /// Get the probability that the countermeasure stops a weapon of a category
+    ///
+    /// See [Interception::against]
+    pub fn interception_probability(&self, category: WeaponCategory) -> f32 {
+        self.interception.against(category)
+    }
+
+    /// Get the susceptibility of the missiles to the countermeasure
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use weapons::countermeasures::{Countermeasure, CountermeasureType, Susceptibility};
+    ///
+    /// let mut chaff = Countermeasure::new(CountermeasureType::Chaff);
+    /// assert_eq!(
+    ///     chaff.get_susceptibility(),
+    ///     Susceptibility::of(CountermeasureType::Chaff)
+    /// );
+    ///
+    /// chaff.set_susceptibility(Some(Susceptibility::default()));
+    /// assert_eq!(chaff.get_susceptibility(), Susceptibility::default());
+    /// ```
+    pub fn get_susceptibility(&self) -> Susceptibility {
+        self.susceptibility
+            .apply(Susceptibility::of(self.countermeasure_type))
+    }
+
+    /// Override the susceptibility of the missiles to the countermeasure
+    ///
+    /// With None, the default susceptibility of the countermeasure type is used.
+    pub fn set_susceptibility(&mut self, susceptibility: Option<Susceptibility>) {
+        self.susceptibility = susceptibility.map(Into::into).unwrap_or_default();
+    }
+
+    /// Get the susceptibility values overridden by the countermeasure
+    pub fn get_susceptibility_override(&self) -> &SusceptibilityOverride {
+        &self.susceptibility
+    }
+
+    /// Get the susceptibility values overridden by the countermeasure with a mutable reference
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use weapons::countermeasures::{Countermeasure, CountermeasureType};
+    ///
+    /// let mut jammer = Countermeasure::new(CountermeasureType::Jammer);
+    /// jammer.get_susceptibility_override_mut().gps = Some(0.0);
+    /// assert_eq!(jammer.get_susceptibility().gps, 0.0);
+    /// assert_eq!(jammer.get_susceptibility().radio, 1.0);
+    /// ```
+    pub fn get_susceptibility_override_mut(&mut self) -> &mut SusceptibilityOverride {
+        &mut self.susceptibility
+    }
+
+    /// Get the probability that the countermeasure stops a missile, depending on its guidance
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use weapons::countermeasures::{Countermeasure, CountermeasureType};
+    /// use weapons::missiles::{Missile, MissileGuidanceType, ProjectileType};
+    ///
+    /// let mut flares = Countermeasure::new(CountermeasureType::Flares);
+    /// flares.get_interception_mut().missile = 0.5;
+    ///
+    /// let heat = Missile::new(MissileGuidanceType::Heat, ProjectileType::Cruise);
+    /// let radar = Missile::new(MissileGuidanceType::Radar, ProjectileType::Cruise);
+    /// assert_eq!(flares.effective_interception(&heat), 0.45);
+    /// assert_eq!(flares.effective_interception(&radar), 0.0);
+    /// ```
+    pub fn effective_interception(&self, missile: &Missile) -> f32 {
+        self.interception_probability(WeaponCategory::Missile)
+            * self
+                .get_susceptibility()
+                .against(missile.get_missile_type())
+    }
+
+    /// Get the information on the countermeasure
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use weapons::countermeasures::{Countermeasure, CountermeasureType};
+    /// use weapons::WeaponInformations;
+    ///
+    /// let jammer = Countermeasure::new(CountermeasureType::Jammer);
+    /// assert_eq!(jammer.get_informations(), &WeaponInformations::default());
+    /// ```
+    pub fn get_informations(&self) -> &WeaponInformations {
+        &self.informations
+    }
+
+    /// Get the information on the countermeasure with a mutable reference
+    ///
+    /// See Self::get_informations
+    pub fn get_informations_mut(&mut self) -> &mut WeaponInformations {
+        &mut self.informations
+    }
+}
+
+#[cfg(test)]
+mod test {
+    #[test]
+    fn test_interception_is_clamped() {
+        use super::*;
+
+        let interception = Interception {
+            missile: 1.5,
+            shell: -0.5,
+            ..Default::default()
+        };
+        assert_eq!(interception.against(WeaponCategory::Missile), 1.0);
+        assert_eq!(interception.against(WeaponCategory::Shell), 0.0);
+    }
+
+    #[test]
+    fn test_firearms_are_not_intercepted() {
+        use super::*;
+
+        let mut ciws = Countermeasure::new(CountermeasureType::Ciws);
+        ciws.set_interception(Interception {
+            missile: 1.0,
+            torpedo: 1.0,
+            shell: 1.0,
+            bullet: 1.0,
+            bomb: 1.0,
+            drone: 1.0,
+        });
+        assert_eq!(ciws.interception_probability(WeaponCategory::FireArm), 0.0);
+        assert_eq!(ciws.interception_probability(WeaponCategory::Bullet), 1.0);
+    }
+
+    #[cfg(feature = "config")]
+    #[test]
+    fn test_deserialize_from_id() {
+        use super::*;
+
+        let countermeasure: Countermeasure = serde_json::from_str(
+            r#"{ "countermeasure_type": 1, "informations": { "name": "Flares", "country_reference": "fr" } }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            countermeasure.get_countermeasure_type(),
+            CountermeasureType::Flares
+        );
+        assert_eq!(countermeasure.get_interception(), &Interception::default());
+        assert_eq!(
+            countermeasure.get_susceptibility(),
+            Susceptibility::of(CountermeasureType::Flares)
+        );
+    }
+
+    #[cfg(feature = "config")]
+    #[test]
+    fn test_partial_susceptibility_override() {
+        use super::*;
+        use crate::missiles::ProjectileType;
+
+        let mut ciws: Countermeasure = toml::from_str(
+            r#"
+            countermeasure_type = 0
+            interception = { missile = 0.8 }
+            susceptibility = { heat = 0.5 }
+            informations = { name = "Phalanx", country_reference = "us" }
+            "#,
+        )
+        .unwrap();
+        let radar = Missile::new(MissileGuidanceType::Radar, ProjectileType::Cruise);
+        let heat = Missile::new(MissileGuidanceType::Heat, ProjectileType::Cruise);
+        assert_eq!(ciws.effective_interception(&radar), 0.8);
+        assert_eq!(ciws.effective_interception(&heat), 0.4);
+
+        ciws.set_susceptibility(None);
+        assert_eq!(ciws.effective_interception(&heat), 0.8);
+    }
+
+    #[test]
+    fn test_guided_weapons_are_always_hit_by_guns() {
+        use super::*;
+        use crate::missiles::ProjectileType;
+
+        let mut ciws = Countermeasure::new(CountermeasureType::Ciws);
+        ciws.get_interception_mut().missile = 0.3;
+        let mut jammer = Countermeasure::new(CountermeasureType::Jammer);
+        jammer.get_interception_mut().missile = 0.3;
+
+        let laser = Missile::new(MissileGuidanceType::Laser, ProjectileType::Cruise);
+        assert_eq!(ciws.effective_interception(&laser), 0.3);
+        assert_eq!(jammer.effective_interception(&laser), 0.0);
+    }
+}
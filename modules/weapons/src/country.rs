@@ -0,0 +1,200 @@
+//! This module define the countries that can use weapons
+//!
+//! Countries are identified by their ISO 3166-1 code, either alpha-2 (`fr`) or alpha-3 (`fra`),
+//! stored in lowercase.
+
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use crate::{WeaponID, WeaponRef, WeaponStore};
+use serde::{Deserialize, Serialize};
+
+/// The id of a country
+///
+/// # Example
+///
+/// ```
+/// use weapons::country::CountryId;
+///
+/// let france: CountryId = "FR".parse().unwrap();
+/// assert_eq!(france.as_str(), "fr");
+/// assert!("France".parse::<CountryId>().is_err());
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct CountryId(String);
+
+impl CountryId {
+    /// Create a new country id, return an error if it isn't made of 2 or 3 ASCII letters
+    pub fn new(id: impl Into<String>) -> Result<Self, InvalidCountryId> {
+        let id = id.into();
+        let valid = (2..=3).contains(&id.len()) && id.chars().all(|c| c.is_ascii_alphabetic());
+        if !valid {
+            return Err(InvalidCountryId(id));
+        }
+        Ok(Self(id.to_ascii_lowercase()))
+    }
+
+    /// Get the id as a string
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl FromStr for CountryId {
+    type Err = InvalidCountryId;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::new(s)
+    }
+}
+
+impl TryFrom<String> for CountryId {
+    type Error = InvalidCountryId;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::new(value)
+    }
+}
+
+impl From<CountryId> for String {
+    fn from(value: CountryId) -> Self {
+        value.0
+    }
+}
+
+impl Display for CountryId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Error returned when a country id isn't valid
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InvalidCountryId(pub String);
+
+impl Display for InvalidCountryId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "invalid country id '{}'", self.0)
+    }
+}
+
+impl std::error::Error for InvalidCountryId {}
+
+/// Contains every known country with its name
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct CountryRegistry {
+    countries: HashMap<CountryId, String>,
+}
+
+impl CountryRegistry {
+    /// Register a country, replacing its name if it was already known
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use weapons::country::{CountryId, CountryRegistry};
+    ///
+    /// let france: CountryId = "fr".parse().unwrap();
+    /// let mut registry = CountryRegistry::default();
+    /// registry.register(france.clone(), "France");
+    /// assert_eq!(registry.get_name(&france), Some("France"));
+    /// assert!(registry.contains(&france));
+    /// ```
+    pub fn register(&mut self, id: CountryId, name: impl Into<String>) {
+        self.countries.insert(id, name.into());
+    }
+
+    /// Remove a country from the registry
+    pub fn unregister(&mut self, id: &CountryId) {
+        self.countries.remove(id);
+    }
+
+    /// Get the name of a country
+    pub fn get_name(&self, id: &CountryId) -> Option<&str> {
+        self.countries.get(id).map(String::as_str)
+    }
+
+    /// Return a boolean that indicates if the country is known
+    pub fn contains(&self, id: &CountryId) -> bool {
+        self.countries.contains_key(id)
+    }
+
+    /// Iterate over every known country
+    pub fn iter(&self) -> impl Iterator<Item = (&CountryId, &str)> {
+        self.countries.iter().map(|(id, name)| (id, name.as_str()))
+    }
+
+    /// Get the number of known countries
+    pub fn len(&self) -> usize {
+        self.countries.len()
+    }
+
+    /// Return a boolean that indicates if no country is known
+    pub fn is_empty(&self) -> bool {
+        self.countries.is_empty()
+    }
+}
+
+impl WeaponStore {
+    /// Iterate over the weapons that a country can use
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use weapons::bullets::{Bullet, BulletType};
+    /// use weapons::WeaponStore;
+    ///
+    /// let mut store = WeaponStore::default();
+    /// let mut bullet = Bullet::new(BulletType::Ordinary);
+    /// bullet.get_informations_mut().country_reference = Some("fr".parse().unwrap());
+    /// store.insert("5.56", bullet);
+    /// store.insert("7.62", Bullet::new(BulletType::Ordinary));
+    ///
+    /// let france = "fr".parse().unwrap();
+    /// let weapons: Vec<_> = store.weapons_for_country(&france).map(|(id, _)| id).collect();
+    /// assert_eq!(weapons, vec!["5.56"]);
+    /// ```
+    pub fn weapons_for_country<'a>(
+        &'a self,
+        country: &'a CountryId,
+    ) -> impl Iterator<Item = (&'a WeaponID, WeaponRef<'a>)> {
+        self.iter().filter(move |(_, weapon)| {
+            weapon
+                .weapon()
+                .get_informations()
+                .country_reference
+                .as_ref()
+                == Some(country)
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    #[test]
+    fn test_country_id_validation() {
+        use super::*;
+
+        assert_eq!(CountryId::new("FRA").unwrap().as_str(), "fra");
+        assert!(CountryId::new("").is_err());
+        assert!(CountryId::new("f").is_err());
+        assert!(CountryId::new("fr1").is_err());
+        assert!(CountryId::new("é").is_err());
+    }
+
+    #[cfg(feature = "config")]
+    #[test]
+    fn test_deserialize() {
+        use super::*;
+
+        let registry: CountryRegistry =
+            toml::from_str("fr = \"France\"\nDE = \"Germany\"").unwrap();
+        assert_eq!(registry.len(), 2);
+        assert_eq!(registry.get_name(&"de".parse().unwrap()), Some("Germany"));
+
+        assert!(toml::from_str::<CountryRegistry>("france = \"France\"").is_err());
+    }
+}
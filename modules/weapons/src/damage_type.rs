@@ -0,0 +1,322 @@
+//! This module define the nature of the damages dealt by weapons
+//!
+//! The damages of a weapon are split between several damage types, and each target is more or
+//! less vulnerable to each type. For example an EMP destroys the electronics of a satellite but
+//! doesn't hurt infantry.
+
+use crate::{Damages, TargetKind};
+use serde::{Deserialize, Serialize};
+
+/// The nature of damages
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[repr(u8)]
+pub enum DamageType {
+    /// Damages dealt by the impact of a projectile
+    Kinetic = 0,
+    /// Damages dealt by a blast
+    Explosive = 1,
+    /// Damages dealt by fire
+    Incendiary = 2,
+    /// Electromagnetic pulse, damaging electronic devices
+    Emp = 3,
+}
+
+impl DamageType {
+    /// Every damage type
+    pub const ALL: [DamageType; 4] = [
+        DamageType::Kinetic,
+        DamageType::Explosive,
+        DamageType::Incendiary,
+        DamageType::Emp,
+    ];
+}
+
+impl TryFrom<i64> for DamageType {
+    type Error = ();
+
+    fn try_from(value: i64) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Self::Kinetic),
+            1 => Ok(Self::Explosive),
+            2 => Ok(Self::Incendiary),
+            3 => Ok(Self::Emp),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Define how the damages of a weapon are split between the damage types
+///
+/// The values are weights: `{ kinetic = 1, incendiary = 1 }` means half kinetic and half
+/// incendiary damages. If every weight is 0, the damages are kinetic.
+#[derive(Clone, Default, Debug, Serialize, Deserialize, PartialEq, PartialOrd, Copy)]
+pub struct DamageTypes {
+    #[serde(default)]
+    pub kinetic: f32,
+    #[serde(default)]
+    pub explosive: f32,
+    #[serde(default)]
+    pub incendiary: f32,
+    #[serde(default)]
+    pub emp: f32,
+}
+
+impl DamageTypes {
+    /// Get the weight of a damage type, negative weights are ignored
+    pub fn weight(&self, damage_type: DamageType) -> f32 {
+        let weight = match damage_type {
+            DamageType::Kinetic => self.kinetic,
+            DamageType::Explosive => self.explosive,
+            DamageType::Incendiary => self.incendiary,
+            DamageType::Emp => self.emp,
+        };
+        weight.max(0.0)
+    }
+
+    /// Get the part (from 0.0 to 1.0) of the damages of a type
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use weapons::damage_type::{DamageType, DamageTypes};
+    ///
+    /// let incendiary = DamageTypes {
+    ///     kinetic: 3.0,
+    ///     incendiary: 1.0,
+    ///     ..Default::default()
+    /// };
+    /// assert_eq!(incendiary.share(DamageType::Incendiary), 0.25);
+    ///
+    /// // without any weight, everything is kinetic
+    /// assert_eq!(DamageTypes::default().share(DamageType::Kinetic), 1.0);
+    /// ```
+    pub fn share(&self, damage_type: DamageType) -> f32 {
+        let total: f32 = DamageType::ALL.iter().map(|t| self.weight(*t)).sum();
+        if total <= 0.0 {
+            return if damage_type == DamageType::Kinetic {
+                1.0
+            } else {
+                0.0
+            };
+        }
+        self.weight(damage_type) / total
+    }
+
+    /// Get the average vulnerability of a target to these damages
+    pub fn vulnerability(&self, vulnerability: &Vulnerability) -> f32 {
+        DamageType::ALL
+            .iter()
+            .map(|t| self.share(*t) * vulnerability.against(*t))
+            .sum()
+    }
+}
+
+/// Define the multiplier applied to each damage type when it hits a target
+///
+/// Missing values are 1.0, the damages are unchanged.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, PartialOrd, Copy)]
+#[serde(default)]
+pub struct Vulnerability {
+    pub kinetic: f32,
+    pub explosive: f32,
+    pub incendiary: f32,
+    pub emp: f32,
+}
+
+impl Default for Vulnerability {
+    fn default() -> Self {
+        Self {
+            kinetic: 1.0,
+            explosive: 1.0,
+            incendiary: 1.0,
+            emp: 1.0,
+        }
+    }
+}
+
+impl Vulnerability {
+    /// Get the multiplier of a damage type, negative multipliers are ignored
+    pub fn against(&self, damage_type: DamageType) -> f32 {
+        let multiplier = match damage_type {
+            DamageType::Kinetic => self.kinetic,
+            DamageType::Explosive => self.explosive,
+            DamageType::Incendiary => self.incendiary,
+            DamageType::Emp => self.emp,
+        };
+        multiplier.max(0.0)
+    }
+}
+
+impl Damages {
+    /// Get the damages of a type that the weapon can do to a kind of target
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use weapons::damage_type::{DamageType, DamageTypes};
+    /// use weapons::{Damages, TargetKind};
+    ///
+    /// let damages = Damages {
+    ///     satellite: 10.0,
+    ///     types: DamageTypes {
+    ///         emp: 1.0,
+    ///         ..Default::default()
+    ///     },
+    ///     ..Default::default()
+    /// };
+    /// assert_eq!(damages.against_type(TargetKind::Satellite, DamageType::Emp), 10.0);
+    /// assert_eq!(damages.against_type(TargetKind::Satellite, DamageType::Kinetic), 0.0);
+    /// ```
+    pub fn against_type(&self, target: TargetKind, damage_type: DamageType) -> f32 {
+        self.against(target) * self.types.share(damage_type)
+    }
+
+    /// Iterate over the damages of each type that the weapon can do to a kind of target
+    pub fn by_type(&self, target: TargetKind) -> impl Iterator<Item = (DamageType, f32)> + '_ {
+        DamageType::ALL
+            .into_iter()
+            .map(move |t| (t, self.against_type(target, t)))
+    }
+
+    /// Get the damages that the weapon can do to a target, after applying its vulnerability
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use weapons::damage_type::{DamageTypes, Vulnerability};
+    /// use weapons::{Damages, TargetKind};
+    ///
+    /// let damages = Damages {
+    ///     infantry: 4.0,
+    ///     types: DamageTypes {
+    ///         kinetic: 1.0,
+    ///         emp: 1.0,
+    ///         ..Default::default()
+    ///     },
+    ///     ..Default::default()
+    /// };
+    /// let infantry = Vulnerability {
+    ///     emp: 0.0,
+    ///     ..Default::default()
+    /// };
+    /// assert_eq!(damages.effective_against(TargetKind::Infantry, &infantry), 2.0);
+    /// ```
+    pub fn effective_against(&self, target: TargetKind, vulnerability: &Vulnerability) -> f32 {
+        self.against(target) * self.types.vulnerability(vulnerability)
+    }
+
+    /// Get the sum of the damages on every kind of target
+    pub fn total(&self) -> f32 {
+        self.building
+            + self.infantry
+            + self.vehicle
+            + self.armored_vehicle
+            + self.tank
+            + self.helicopter
+            + self.plane
+            + self.ship
+            + self.submarine
+            + self.missile
+            + self.satellite
+    }
+
+    /// Combine the damages of two weapons hitting together, like the warheads of a missile
+    ///
+    /// The damage types of the result are weighted by the total damages of each weapon.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use weapons::damage_type::{DamageType, DamageTypes};
+    /// use weapons::Damages;
+    ///
+    /// let bullet = Damages {
+    ///     infantry: 1.0,
+    ///     ..Default::default()
+    /// };
+    /// let fire = Damages {
+    ///     infantry: 1.0,
+    ///     types: DamageTypes {
+    ///         incendiary: 1.0,
+    ///         ..Default::default()
+    ///     },
+    ///     ..Default::default()
+    /// };
+    ///
+    /// let combined = bullet.combine(&fire);
+    /// assert_eq!(combined.infantry, 2.0);
+    /// assert_eq!(combined.types.share(DamageType::Incendiary), 0.5);
+    /// ```
+    pub fn combine(&self, other: &Damages) -> Damages {
+        let weight = |t| {
+            self.types.share(t) * self.total().max(0.0)
+                + other.types.share(t) * other.total().max(0.0)
+        };
+
+        Damages {
+            building: self.building + other.building,
+            infantry: self.infantry + other.infantry,
+            vehicle: self.vehicle + other.vehicle,
+            armored_vehicle: self.armored_vehicle + other.armored_vehicle,
+            tank: self.tank + other.tank,
+            helicopter: self.helicopter + other.helicopter,
+            plane: self.plane + other.plane,
+            ship: self.ship + other.ship,
+            submarine: self.submarine + other.submarine,
+            missile: self.missile + other.missile,
+            satellite: self.satellite + other.satellite,
+            types: DamageTypes {
+                kinetic: weight(DamageType::Kinetic),
+                explosive: weight(DamageType::Explosive),
+                incendiary: weight(DamageType::Incendiary),
+                emp: weight(DamageType::Emp),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    #[test]
+    fn test_by_type_sums_to_damages() {
+        use super::*;
+
+        let damages = Damages {
+            vehicle: 6.0,
+            types: DamageTypes {
+                kinetic: 1.0,
+                explosive: 2.0,
+                incendiary: -4.0,
+                emp: 3.0,
+            },
+            ..Default::default()
+        };
+        let by_type: Vec<_> = damages.by_type(TargetKind::Vehicle).collect();
+        assert_eq!(
+            by_type,
+            vec![
+                (DamageType::Kinetic, 1.0),
+                (DamageType::Explosive, 2.0),
+                (DamageType::Incendiary, 0.0),
+                (DamageType::Emp, 3.0),
+            ]
+        );
+    }
+
+    #[cfg(feature = "config")]
+    #[test]
+    fn test_backward_compatible() {
+        use super::*;
+
+        let damages: Damages = toml::from_str("tank = 7.0").unwrap();
+        assert_eq!(damages.types, DamageTypes::default());
+        assert_eq!(
+            damages.against_type(TargetKind::Tank, DamageType::Kinetic),
+            7.0
+        );
+
+        let vulnerability: Vulnerability = toml::from_str("emp = 2.0").unwrap();
+        assert_eq!(vulnerability.kinetic, 1.0);
+        assert_eq!(vulnerability.emp, 2.0);
+    }
+}
@@ -0,0 +1,235 @@
+//! This module define loitering munitions, drones that wait above an area before diving on a
+//! target
+
+use crate::{Damages, Weapon, WeaponCategory, WeaponInformations};
+use serde::{Deserialize, Serialize};
+
+/// Enumeration representing the different ways a drone can be controlled.
+#[derive(Clone, Debug, PartialEq, PartialOrd, Copy, Serialize, Deserialize)]
+#[repr(u8)]
+pub enum ControlLink {
+    /// No link, the drone finds and attacks its target by itself.
+    Autonomous = 0,
+    /// Radio link, the operator must stay in line of sight.
+    Radio = 1,
+    /// Satellite link, can be controlled from anywhere.
+    Satellite = 2,
+    /// Fiber optic cable unrolled behind the drone, can't be jammed but has a limited range.
+    FiberOptic = 3,
+}
+
+impl TryFrom<i64> for ControlLink {
+    type Error = ();
+
+    fn try_from(value: i64) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Self::Autonomous),
+            1 => Ok(Self::Radio),
+            2 => Ok(Self::Satellite),
+            3 => Ok(Self::FiberOptic),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Endurance of a drone in minutes
+pub type Endurance = u32;
+
+/// A loitering munition
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Drone {
+    #[serde(deserialize_with = "crate::deserialize_id")]
+    control_link: ControlLink,
+    /// How long the drone can wait above an area before falling
+    #[serde(default)]
+    endurance: Endurance,
+    /// If the attack can be cancelled after launch
+    #[serde(default)]
+    can_abort: bool,
+    /// If a new target can be chosen after launch
+    #[serde(default)]
+    can_retarget: bool,
+
+    informations: WeaponInformations,
+    damages: Damages,
+}
+
+impl Drone {
+    /// Create a new drone
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use weapons::drones::{ControlLink, Drone};
+    ///
+    /// let drone = Drone::new(ControlLink::Radio, 30);
+    /// assert_eq!(drone.get_control_link(), ControlLink::Radio);
+    /// assert_eq!(drone.get_endurance(), 30);
+    /// assert!(!drone.can_abort());
+    /// assert!(!drone.can_retarget());
+    /// ```
+    pub fn new(control_link: ControlLink, endurance: Endurance) -> Self {
+        Self {
+            control_link,
+            endurance,
+            can_abort: false,
+            can_retarget: false,
+
+            informations: WeaponInformations::default(),
+            damages: Damages::default(),
+        }
+    }
+
+    /// Get the way the drone is controlled
+    pub fn get_control_link(&self) -> ControlLink {
+        self.control_link
+    }
+
+    /// Define a new way to control the drone
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use weapons::drones::{ControlLink, Drone};
+    ///
+    /// let mut drone = Drone::new(ControlLink::Radio, 30);
+    /// drone.set_control_link(ControlLink::FiberOptic);
+    /// assert_eq!(drone.get_control_link(), ControlLink::FiberOptic);
+    /// ```
+    pub fn set_control_link(&mut self, control_link: ControlLink) {
+        self.control_link = control_link;
+    }
+
+    /// Get how long the drone can loiter, in minutes
+    pub fn get_endurance(&self) -> Endurance {
+        self.endurance
+    }
+
+    /// Define how long the drone can loiter, in minutes
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use weapons::drones::{ControlLink, Drone};
+    ///
+    /// let mut drone = Drone::new(ControlLink::Radio, 30);
+    /// drone.set_endurance(120);
+    /// assert_eq!(drone.get_endurance(), 120);
+    /// ```
+    pub fn set_endurance(&mut self, endurance: Endurance) {
+        self.endurance = endurance;
+    }
+
+    /// Return a boolean that indicates if the attack can be cancelled after launch
+    ///
+    /// An autonomous drone can't be aborted since nobody is controlling it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use weapons::drones::{ControlLink, Drone};
+    ///
+    /// let mut drone = Drone::new(ControlLink::Autonomous, 30);
+    /// drone.set_can_abort(true);
+    /// assert!(!drone.can_abort());
+    ///
+    /// drone.set_control_link(ControlLink::Satellite);
+    /// assert!(drone.can_abort());
+    /// ```
+    pub fn can_abort(&self) -> bool {
+        self.can_abort && self.control_link != ControlLink::Autonomous
+    }
+
+    /// Set whether the attack can be cancelled after launch
+    pub fn set_can_abort(&mut self, can_abort: bool) {
+        self.can_abort = can_abort;
+    }
+
+    /// Return a boolean that indicates if a new target can be chosen after launch
+    ///
+    /// An autonomous drone can't be retargeted since nobody is controlling it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use weapons::drones::{ControlLink, Drone};
+    ///
+    /// let mut drone = Drone::new(ControlLink::Radio, 30);
+    /// drone.set_can_retarget(true);
+    /// assert!(drone.can_retarget());
+    /// ```
+    pub fn can_retarget(&self) -> bool {
+        self.can_retarget && self.control_link != ControlLink::Autonomous
+    }
+
+    /// Set whether a new target can be chosen after launch
+    pub fn set_can_retarget(&mut self, can_retarget: bool) {
+        self.can_retarget = can_retarget;
+    }
+
+    /// Get the information on the drone
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use weapons::drones::{ControlLink, Drone};
+    /// use weapons::WeaponInformations;
+    ///
+    /// let drone = Drone::new(ControlLink::Radio, 30);
+    /// assert_eq!(drone.get_informations(), &WeaponInformations::default());
+    /// ```
+    pub fn get_informations(&self) -> &WeaponInformations {
+        &self.informations
+    }
+
+    /// Get the information on the drone with a mutable reference
+    ///
+    /// See Self::get_informations
+    pub fn get_informations_mut(&mut self) -> &mut WeaponInformations {
+        &mut self.informations
+    }
+
+    /// Get the damages given by the drone
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use weapons::drones::{ControlLink, Drone};
+    /// use weapons::Damages;
+    ///
+    /// let drone = Drone::new(ControlLink::Radio, 30);
+    /// assert_eq!(drone.get_damages(), &Damages::default());
+    /// ```
+    pub fn get_damages(&self) -> &Damages {
+        &self.damages
+    }
+
+    /// Get the damages given by the drone with a mutable reference
+    ///
+    /// See Self::get_damages
+    pub fn get_damages_mut(&mut self) -> &mut Damages {
+        &mut self.damages
+    }
+}
+
+impl Weapon for Drone {
+    fn category(&self) -> WeaponCategory {
+        WeaponCategory::Drone
+    }
+
+    fn get_informations(&self) -> &WeaponInformations {
+        &self.informations
+    }
+
+    fn get_informations_mut(&mut self) -> &mut WeaponInformations {
+        &mut self.informations
+    }
+
+    fn get_damages(&self) -> &Damages {
+        &self.damages
+    }
+
+    fn get_damages_mut(&mut self) -> &mut Damages {
+        &mut self.damages
+    }
+}
@@ -0,0 +1,195 @@
+//! This module simulate a missile flying through layers of defenses
+//!
+//! The simulation doesn't use any randomness: it computes the probability that each defense stops
+//! the missile, and the caller rolls the dice with [EngagementOutcome::intercepted_by].
+//!
+//! Hard-kill defenses (guns and interceptors) have less time to engage fast missiles, and
+//! hypersonic missiles are harder to hit since they can maneuver. Soft-kill defenses (decoys and
+//! jammers) only depend on the guidance of the missile.
+
+use crate::countermeasures::{Countermeasure, CountermeasureType};
+use crate::missiles::Missile;
+
+/// Speed in meters per second under which hard-kill defenses have their full efficiency
+pub const REFERENCE_SPEED: f32 = 300.0;
+
+/// Multiplier applied to the hard-kill defenses against hypersonic missiles
+pub const HYPERSONIC_FACTOR: f32 = 0.5;
+
+/// The result of a missile engagement
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct EngagementOutcome {
+    /// The probability that each defense stops the missile if it reaches it, in the order of the
+    /// defenses
+    pub layers: Vec<f32>,
+    /// The probability that the missile goes through every defense
+    pub survival_probability: f32,
+    /// The number of warheads expected to reach the target
+    pub expected_warheads: f32,
+}
+
+impl EngagementOutcome {
+    /// Get the probability that the missile is stopped by any defense
+    pub fn interception_probability(&self) -> f32 {
+        1.0 - self.survival_probability
+    }
+
+    /// Get the index of the defense that stopped the missile, from one roll between 0.0 and 1.0
+    /// for each defense
+    ///
+    /// Return None if the missile went through every defense. Missing rolls are considered as
+    /// failed interceptions.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use weapons::engagement::EngagementOutcome;
+    ///
+    /// let outcome = EngagementOutcome {
+    ///     layers: vec![0.5, 0.8],
+    ///     survival_probability: 0.1,
+    ///     expected_warheads: 0.1,
+    /// };
+    /// assert_eq!(outcome.intercepted_by([0.7, 0.2]), Some(1));
+    /// assert_eq!(outcome.intercepted_by([0.7, 0.9]), None);
+    /// ```
+    pub fn intercepted_by(&self, rolls: impl IntoIterator<Item = f32>) -> Option<usize> {
+        self.layers
+            .iter()
+            .zip(rolls)
+            .position(|(probability, roll)| roll < *probability)
+    }
+}
+
+/// Get the multiplier applied to a defense because of the speed of the missile
+fn speed_factor(missile: &Missile, countermeasure: &Countermeasure) -> f32 {
+    let hard_kill = matches!(
+        countermeasure.get_countermeasure_type(),
+        CountermeasureType::Ciws | CountermeasureType::Interceptor
+    );
+    if !hard_kill {
+        return 1.0;
+    }
+
+    let speed = missile.get_informations().speed;
+    let mut factor = if speed > REFERENCE_SPEED {
+        (REFERENCE_SPEED / speed).sqrt()
+    } else {
+        1.0
+    };
+    if missile.is_hypersonic() {
+        factor *= HYPERSONIC_FACTOR;
+    }
+    factor
+}
+
+/// Simulate a missile going through layers of defenses, from the outermost to the innermost
+///
+/// A missile with a negative speed hits instantly and can't be intercepted.
+///
+/// # Example
+///
+/// ```
+/// use weapons::countermeasures::{Countermeasure, CountermeasureType};
+/// use weapons::engagement::simulate_engagement;
+/// use weapons::missiles::{Missile, MissileGuidanceType, ProjectileType};
+///
+/// let mut interceptor = Countermeasure::new(CountermeasureType::Interceptor);
+/// interceptor.get_interception_mut().missile = 0.8;
+/// let mut ciws = Countermeasure::new(CountermeasureType::Ciws);
+/// ciws.get_interception_mut().missile = 0.5;
+///
+/// let mut exocet = Missile::new(MissileGuidanceType::Radar, ProjectileType::Cruise);
+/// exocet.get_informations_mut().speed = 300.0;
+///
+/// let outcome = simulate_engagement(&exocet, &[interceptor, ciws]);
+/// assert_eq!(outcome.layers, vec![0.8, 0.5]);
+/// assert!((outcome.survival_probability - 0.1).abs() < 1e-6);
+/// ```
+pub fn simulate_engagement(attacker: &Missile, defenses: &[Countermeasure]) -> EngagementOutcome {
+    let instant = attacker.get_informations().speed < 0.0;
+
+    let layers: Vec<f32> = defenses
+        .iter()
+        .map(|defense| {
+            if instant {
+                return 0.0;
+            }
+            let probability =
+                defense.effective_interception(attacker) * speed_factor(attacker, defense);
+            probability.clamp(0.0, 1.0)
+        })
+        .collect();
+
+    let survival_probability = layers.iter().map(|p| 1.0 - p).product::<f32>();
+
+    EngagementOutcome {
+        layers,
+        survival_probability,
+        expected_warheads: survival_probability * attacker.get_warhead_count() as f32,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    fn defense(
+        countermeasure_type: crate::countermeasures::CountermeasureType,
+    ) -> crate::countermeasures::Countermeasure {
+        use super::*;
+
+        let mut defense = Countermeasure::new(countermeasure_type);
+        defense.get_interception_mut().missile = 0.8;
+        defense
+    }
+
+    #[test]
+    fn test_fast_missiles_are_harder_to_intercept() {
+        use super::*;
+        use crate::missiles::{MissileGuidanceType, ProjectileType};
+
+        let defenses = [defense(CountermeasureType::Interceptor)];
+
+        let mut missile = Missile::new(MissileGuidanceType::Gps, ProjectileType::Ballistic);
+        missile.get_informations_mut().speed = 1200.0;
+        let fast = simulate_engagement(&missile, &defenses);
+        assert_eq!(fast.layers, vec![0.4]);
+
+        missile.set_hypersonic(true);
+        let hypersonic = simulate_engagement(&missile, &defenses);
+        assert_eq!(hypersonic.layers, vec![0.2]);
+    }
+
+    #[test]
+    fn test_decoys_only_depend_on_guidance() {
+        use super::*;
+        use crate::missiles::{MissileGuidanceType, ProjectileType};
+
+        let defenses = [
+            defense(CountermeasureType::Flares),
+            defense(CountermeasureType::Chaff),
+        ];
+
+        let mut missile = Missile::new(MissileGuidanceType::Heat, ProjectileType::Cruise);
+        missile.get_informations_mut().speed = 1200.0;
+        missile.set_hypersonic(true);
+        missile.set_warhead_count(2);
+
+        let outcome = simulate_engagement(&missile, &defenses);
+        assert!((outcome.layers[0] - 0.72).abs() < 1e-6);
+        assert_eq!(outcome.layers[1], 0.0);
+        assert!((outcome.expected_warheads - 0.56).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_instant_missiles_are_never_intercepted() {
+        use super::*;
+        use crate::missiles::{MissileGuidanceType, ProjectileType};
+
+        let mut missile = Missile::new(MissileGuidanceType::Radio, ProjectileType::Cruise);
+        missile.get_informations_mut().speed = -1.0;
+
+        let outcome = simulate_engagement(&missile, &[defense(CountermeasureType::Ciws)]);
+        assert_eq!(outcome.survival_probability, 1.0);
+        assert_eq!(outcome.intercepted_by([0.0]), None);
+    }
+}
@@ -1,5 +1,5 @@
 use crate::WeaponID;
-use crate::{Damages, WeaponInformations};
+use crate::{Damages, Weapon, WeaponCategory, WeaponInformations};
 use serde::{Deserialize, Serialize};
 
 /// Enumeration representing different types of firearms.
@@ -24,10 +24,27 @@ pub enum FireArmType {
     PrecisionRifle = 5,
 }
 
+impl TryFrom<i64> for FireArmType {
+    type Error = ();
+
+    fn try_from(value: i64) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Self::Gun),
+            1 => Ok(Self::Rifle),
+            2 => Ok(Self::SubMachineGun),
+            3 => Ok(Self::Assault),
+            4 => Ok(Self::MachineGun),
+            5 => Ok(Self::PrecisionRifle),
+            _ => Err(()),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, PartialOrd)]
 pub struct FireArm {
     /// Contain a list of IDs to get the allowed bullets
     allowed_bullets: Vec<WeaponID>,
+    #[serde(deserialize_with = "crate::deserialize_id")]
     fire_arm_type: FireArmType,
     default_bullets: WeaponID,
 
@@ -248,3 +265,25 @@ impl FireArm {
         &mut self.damages
     }
 }
+
+impl Weapon for FireArm {
+    fn category(&self) -> WeaponCategory {
+        WeaponCategory::FireArm
+    }
+
+    fn get_informations(&self) -> &WeaponInformations {
+        &self.informations
+    }
+
+    fn get_informations_mut(&mut self) -> &mut WeaponInformations {
+        &mut self.informations
+    }
+
+    fn get_damages(&self) -> &Damages {
+        &self.damages
+    }
+
+    fn get_damages_mut(&mut self) -> &mut Damages {
+        &mut self.damages
+    }
+}
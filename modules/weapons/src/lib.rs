@@ -1,17 +1,43 @@
 use std::collections::HashMap;
 
+use crate::accuracy::Accuracy;
+use crate::bombs::Bomb;
 use crate::bullets::Bullet;
+use crate::condition::Maintenance;
+use crate::cost::Cost;
+use crate::country::CountryId;
+use crate::damage_type::DamageTypes;
+use crate::drones::Drone;
 use crate::firearm::FireArm;
 use crate::missiles::Missile;
+use crate::requirements::Requirements;
 use crate::shells::Shell;
 use crate::torpedo::Torpedo;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
 
+pub mod accuracy;
+pub mod bombs;
 pub mod bullets;
+pub mod combat;
+pub mod condition;
+pub mod cost;
+pub mod countermeasures;
+pub mod country;
+pub mod damage_type;
+pub mod drones;
+pub mod engagement;
 pub mod firearm;
+#[cfg(feature = "config")]
+pub mod loader;
+pub mod migration;
 pub mod missiles;
+pub mod requirements;
 pub mod shells;
+pub mod stockpile;
 pub mod torpedo;
+pub mod validation;
+#[cfg(feature = "watch")]
+pub mod watch;
 
 /// Speed in meters per second
 ///
@@ -20,17 +46,304 @@ pub type Speed = f32;
 
 pub type WeaponID = String;
 
+/// A weapon of any category, owned
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum WeaponKind {
+    Missile(Missile),
+    Torpedo(Torpedo),
+    Shell(Shell),
+    FireArm(FireArm),
+    Bullet(Bullet),
+    Bomb(Bomb),
+    Drone(Drone),
+}
+
+impl WeaponKind {
+    /// Get the weapon as a trait object
+    pub fn weapon(&self) -> &dyn Weapon {
+        match self {
+            Self::Missile(w) => w,
+            Self::Torpedo(w) => w,
+            Self::Shell(w) => w,
+            Self::FireArm(w) => w,
+            Self::Bullet(w) => w,
+            Self::Bomb(w) => w,
+            Self::Drone(w) => w,
+        }
+    }
+}
+
+impl From<Missile> for WeaponKind {
+    fn from(value: Missile) -> Self {
+        Self::Missile(value)
+    }
+}
+
+impl From<Torpedo> for WeaponKind {
+    fn from(value: Torpedo) -> Self {
+        Self::Torpedo(value)
+    }
+}
+
+impl From<Shell> for WeaponKind {
+    fn from(value: Shell) -> Self {
+        Self::Shell(value)
+    }
+}
+
+impl From<FireArm> for WeaponKind {
+    fn from(value: FireArm) -> Self {
+        Self::FireArm(value)
+    }
+}
+
+impl From<Bullet> for WeaponKind {
+    fn from(value: Bullet) -> Self {
+        Self::Bullet(value)
+    }
+}
+
+impl From<Bomb> for WeaponKind {
+    fn from(value: Bomb) -> Self {
+        Self::Bomb(value)
+    }
+}
+
+impl From<Drone> for WeaponKind {
+    fn from(value: Drone) -> Self {
+        Self::Drone(value)
+    }
+}
+
+/// A reference to a weapon of any category
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum WeaponRef<'a> {
+    Missile(&'a Missile),
+    Torpedo(&'a Torpedo),
+    Shell(&'a Shell),
+    FireArm(&'a FireArm),
+    Bullet(&'a Bullet),
+    Bomb(&'a Bomb),
+    Drone(&'a Drone),
+}
+
+impl<'a> WeaponRef<'a> {
+    /// Get the weapon as a trait object
+    pub fn weapon(&self) -> &'a dyn Weapon {
+        match *self {
+            Self::Missile(w) => w,
+            Self::Torpedo(w) => w,
+            Self::Shell(w) => w,
+            Self::FireArm(w) => w,
+            Self::Bullet(w) => w,
+            Self::Bomb(w) => w,
+            Self::Drone(w) => w,
+        }
+    }
+}
+
 /// Contains every weapon
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+///
+/// A deserialized store is upgraded to the current version, see [migration].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(try_from = "serde_json::Value")]
 pub struct WeaponStore {
+    /// The version of the format of the store, see [migration]
+    version: u32,
     missiles: HashMap<WeaponID, Missile>,
     torpedoes: HashMap<WeaponID, Torpedo>,
     shells: HashMap<WeaponID, Shell>,
     firearm: HashMap<WeaponID, FireArm>,
     bullets: HashMap<WeaponID, Bullet>,
+    bombs: HashMap<WeaponID, Bomb>,
+    drones: HashMap<WeaponID, Drone>,
+}
+
+impl Default for WeaponStore {
+    fn default() -> Self {
+        Self {
+            version: migration::CURRENT_VERSION,
+            missiles: HashMap::new(),
+            torpedoes: HashMap::new(),
+            shells: HashMap::new(),
+            firearm: HashMap::new(),
+            bullets: HashMap::new(),
+            bombs: HashMap::new(),
+            drones: HashMap::new(),
+        }
+    }
 }
 
 impl WeaponStore {
+    /// Get a weapon by its id, whatever its category
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use weapons::shells::{Shell, ShellType};
+    /// use weapons::{WeaponCategory, WeaponStore};
+    ///
+    /// let mut store = WeaponStore::default();
+    /// store.insert("shard", Shell::new(ShellType::ArmorPiercing));
+    ///
+    /// let shard = store.get("shard").unwrap();
+    /// assert_eq!(shard.weapon().category(), WeaponCategory::Shell);
+    /// assert!(store.get("m51").is_none());
+    /// ```
+    pub fn get(&self, id: impl Into<WeaponID>) -> Option<WeaponRef<'_>> {
+        let id = id.into();
+        if let Some(w) = self.missiles.get(&id) {
+            return Some(WeaponRef::Missile(w));
+        }
+        if let Some(w) = self.torpedoes.get(&id) {
+            return Some(WeaponRef::Torpedo(w));
+        }
+        if let Some(w) = self.shells.get(&id) {
+            return Some(WeaponRef::Shell(w));
+        }
+        if let Some(w) = self.firearm.get(&id) {
+            return Some(WeaponRef::FireArm(w));
+        }
+        if let Some(w) = self.bullets.get(&id) {
+            return Some(WeaponRef::Bullet(w));
+        }
+        if let Some(w) = self.bombs.get(&id) {
+            return Some(WeaponRef::Bomb(w));
+        }
+        self.drones.get(&id).map(WeaponRef::Drone)
+    }
+
+    /// Add a weapon to the store in its category
+    ///
+    /// If a weapon with the same id already exists, whatever its category, it is replaced and
+    /// returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use weapons::bullets::{Bullet, BulletType};
+    /// use weapons::shells::{Shell, ShellType};
+    /// use weapons::{WeaponKind, WeaponStore};
+    ///
+    /// let mut store = WeaponStore::default();
+    /// assert!(store.insert("a", Shell::new(ShellType::Mortar)).is_none());
+    ///
+    /// let previous = store.insert("a", Bullet::new(BulletType::Tracing));
+    /// assert!(matches!(previous, Some(WeaponKind::Shell(_))));
+    /// assert_eq!(store.len(), 1);
+    /// ```
+    pub fn insert(
+        &mut self,
+        id: impl Into<WeaponID>,
+        weapon: impl Into<WeaponKind>,
+    ) -> Option<WeaponKind> {
+        let id = id.into();
+        let previous = self.remove(id.clone());
+        match weapon.into() {
+            WeaponKind::Missile(w) => self.add_missile(id, w),
+            WeaponKind::Torpedo(w) => self.add_torpedo(id, w),
+            WeaponKind::Shell(w) => self.add_shell(id, w),
+            WeaponKind::FireArm(w) => self.add_firearm(id, w),
+            WeaponKind::Bullet(w) => self.add_bullet(id, w),
+            WeaponKind::Bomb(w) => self.add_bomb(id, w),
+            WeaponKind::Drone(w) => self.add_drone(id, w),
+        }
+        previous
+    }
+
+    /// Remove a weapon by its id, whatever its category
+    ///
+    /// If several categories contain the id, the weapon is removed from all of them and the
+    /// first one found (in the order of [Self::get]) is returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use weapons::bullets::{Bullet, BulletType};
+    /// use weapons::shells::{Shell, ShellType};
+    /// use weapons::{WeaponKind, WeaponStore};
+    ///
+    /// let mut store = WeaponStore::default();
+    /// store.add_shell("a", Shell::new(ShellType::Mortar));
+    /// store.add_bullet("a", Bullet::new(BulletType::Tracing));
+    ///
+    /// assert!(matches!(store.remove("a"), Some(WeaponKind::Shell(_))));
+    /// assert!(store.is_empty());
+    /// ```
+    pub fn remove(&mut self, id: impl Into<WeaponID>) -> Option<WeaponKind> {
+        let id = id.into();
+        let removed = [
+            self.missiles.remove(&id).map(WeaponKind::Missile),
+            self.torpedoes.remove(&id).map(WeaponKind::Torpedo),
+            self.shells.remove(&id).map(WeaponKind::Shell),
+            self.firearm.remove(&id).map(WeaponKind::FireArm),
+            self.bullets.remove(&id).map(WeaponKind::Bullet),
+            self.bombs.remove(&id).map(WeaponKind::Bomb),
+            self.drones.remove(&id).map(WeaponKind::Drone),
+        ];
+        removed.into_iter().flatten().next()
+    }
+
+    /// Iterate over every weapon of the store, whatever its category
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use weapons::bullets::{Bullet, BulletType};
+    /// use weapons::shells::{Shell, ShellType};
+    /// use weapons::WeaponStore;
+    ///
+    /// let mut store = WeaponStore::default();
+    /// store.insert("a", Shell::new(ShellType::Mortar));
+    /// store.insert("b", Bullet::new(BulletType::Tracing));
+    ///
+    /// assert_eq!(store.iter().count(), 2);
+    /// ```
+    pub fn iter(&self) -> impl Iterator<Item = (&WeaponID, WeaponRef<'_>)> {
+        self.missiles
+            .iter()
+            .map(|(id, w)| (id, WeaponRef::Missile(w)))
+            .chain(
+                self.torpedoes
+                    .iter()
+                    .map(|(id, w)| (id, WeaponRef::Torpedo(w))),
+            )
+            .chain(self.shells.iter().map(|(id, w)| (id, WeaponRef::Shell(w))))
+            .chain(
+                self.firearm
+                    .iter()
+                    .map(|(id, w)| (id, WeaponRef::FireArm(w))),
+            )
+            .chain(
+                self.bullets
+                    .iter()
+                    .map(|(id, w)| (id, WeaponRef::Bullet(w))),
+            )
+            .chain(self.bombs.iter().map(|(id, w)| (id, WeaponRef::Bomb(w))))
+            .chain(self.drones.iter().map(|(id, w)| (id, WeaponRef::Drone(w))))
+    }
+
+    /// Get the number of weapons in the store, whatever their category
+    pub fn len(&self) -> usize {
+        self.missiles.len()
+            + self.torpedoes.len()
+            + self.shells.len()
+            + self.firearm.len()
+            + self.bullets.len()
+            + self.bombs.len()
+            + self.drones.len()
+    }
+
+    /// Return true if the store doesn't contain any weapon
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Return true if a weapon with this id exists, whatever its category
+    pub fn contains(&self, id: impl Into<WeaponID>) -> bool {
+        self.get(id).is_some()
+    }
+
     /// Get all missiles
     pub fn get_missiles(&self) -> &HashMap<WeaponID, Missile> {
         &self.missiles
@@ -180,6 +493,194 @@ impl WeaponStore {
     pub fn remove_bullet(&mut self, id: impl Into<WeaponID>) {
         self.bullets.remove(&id.into());
     }
+
+    /// Get all bombs
+    pub fn get_bombs(&self) -> &HashMap<WeaponID, Bomb> {
+        &self.bombs
+    }
+
+    /// Get all bombs with a mutable reference
+    pub fn get_bombs_mut(&mut self) -> &mut HashMap<WeaponID, Bomb> {
+        &mut self.bombs
+    }
+
+    /// Get a bomb by its id
+    pub fn get_bomb(&self, id: impl Into<WeaponID>) -> Option<&Bomb> {
+        self.bombs.get(&id.into())
+    }
+
+    /// Get a bomb by its id with a mutable reference
+    pub fn get_bomb_mut(&mut self, id: impl Into<WeaponID>) -> Option<&mut Bomb> {
+        self.bombs.get_mut(&id.into())
+    }
+
+    /// Add a bomb to the store
+    pub fn add_bomb(&mut self, id: impl Into<WeaponID>, bomb: Bomb) {
+        self.bombs.insert(id.into(), bomb);
+    }
+
+    /// Remove a bomb from the store
+    pub fn remove_bomb(&mut self, id: impl Into<WeaponID>) {
+        self.bombs.remove(&id.into());
+    }
+
+    /// Get all drones
+    pub fn get_drones(&self) -> &HashMap<WeaponID, Drone> {
+        &self.drones
+    }
+
+    /// Get all drones with a mutable reference
+    pub fn get_drones_mut(&mut self) -> &mut HashMap<WeaponID, Drone> {
+        &mut self.drones
+    }
+
+    /// Get a drone by its id
+    pub fn get_drone(&self, id: impl Into<WeaponID>) -> Option<&Drone> {
+        self.drones.get(&id.into())
+    }
+
+    /// Get a drone by its id with a mutable reference
+    pub fn get_drone_mut(&mut self, id: impl Into<WeaponID>) -> Option<&mut Drone> {
+        self.drones.get_mut(&id.into())
+    }
+
+    /// Add a drone to the store
+    pub fn add_drone(&mut self, id: impl Into<WeaponID>, drone: Drone) {
+        self.drones.insert(id.into(), drone);
+    }
+
+    /// Remove a drone from the store
+    pub fn remove_drone(&mut self, id: impl Into<WeaponID>) {
+        self.drones.remove(&id.into());
+    }
+}
+
+/// The category of a weapon
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[repr(u8)]
+pub enum WeaponCategory {
+    Missile = 0,
+    Torpedo = 1,
+    Shell = 2,
+    FireArm = 3,
+    Bullet = 4,
+    Bomb = 5,
+    Drone = 6,
+}
+
+/// Common behavior of every weapon
+///
+/// It allows generic code to work with any weapon through a `&dyn Weapon`.
+///
+/// # Example
+///
+/// ```
+/// use weapons::bullets::{Bullet, BulletType};
+/// use weapons::missiles::{Missile, MissileGuidanceType, ProjectileType};
+/// use weapons::{Weapon, WeaponCategory};
+///
+/// let mut missile = Missile::new(MissileGuidanceType::Gps, ProjectileType::Ballistic);
+/// missile.get_informations_mut().name = "M51".to_string();
+/// let bullet = Bullet::new(BulletType::Ordinary);
+///
+/// let weapons: Vec<&dyn Weapon> = vec![&missile, &bullet];
+/// assert_eq!(weapons[0].name(), "M51");
+/// assert_eq!(weapons[1].category(), WeaponCategory::Bullet);
+/// ```
+pub trait Weapon {
+    /// Get the category of the weapon
+    fn category(&self) -> WeaponCategory;
+
+    /// Get the information about the weapon
+    fn get_informations(&self) -> &WeaponInformations;
+
+    /// Get the information about the weapon with a mutable reference
+    fn get_informations_mut(&mut self) -> &mut WeaponInformations;
+
+    /// Get the damages of the weapon
+    fn get_damages(&self) -> &Damages;
+
+    /// Get the damages of the weapon with a mutable reference
+    fn get_damages_mut(&mut self) -> &mut Damages;
+
+    /// Get the name of the weapon
+    fn name(&self) -> &str {
+        &self.get_informations().name
+    }
+
+    /// Get the probability to hit a target at a distance in kilometers
+    ///
+    /// See [Accuracy::hit_probability]
+    fn hit_probability(&self, distance: f32, target: TargetKind) -> f32 {
+        self.get_informations()
+            .accuracy
+            .hit_probability(distance, target)
+    }
+}
+
+/// The kind of target a weapon can be used against
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[repr(u8)]
+pub enum TargetKind {
+    Building = 0,
+    Infantry = 1,
+    Vehicle = 2,
+    ArmoredVehicle = 3,
+    Tank = 4,
+    Helicopter = 5,
+    Plane = 6,
+    Ship = 7,
+    Submarine = 8,
+    Missile = 9,
+    Satellite = 10,
+}
+
+impl TargetKind {
+    /// Get the typical radius of the target in meters, used to know how easy it is to hit
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use weapons::TargetKind;
+    ///
+    /// assert!(TargetKind::Ship.radius() > TargetKind::Tank.radius());
+    /// ```
+    pub fn radius(&self) -> f32 {
+        match self {
+            Self::Building => 15.0,
+            Self::Infantry => 0.5,
+            Self::Vehicle => 2.5,
+            Self::ArmoredVehicle => 3.0,
+            Self::Tank => 3.5,
+            Self::Helicopter => 7.0,
+            Self::Plane => 10.0,
+            Self::Ship => 60.0,
+            Self::Submarine => 50.0,
+            Self::Missile => 0.5,
+            Self::Satellite => 3.0,
+        }
+    }
+}
+
+impl TryFrom<i64> for TargetKind {
+    type Error = ();
+
+    fn try_from(value: i64) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Self::Building),
+            1 => Ok(Self::Infantry),
+            2 => Ok(Self::Vehicle),
+            3 => Ok(Self::ArmoredVehicle),
+            4 => Ok(Self::Tank),
+            5 => Ok(Self::Helicopter),
+            6 => Ok(Self::Plane),
+            7 => Ok(Self::Ship),
+            8 => Ok(Self::Submarine),
+            9 => Ok(Self::Missile),
+            10 => Ok(Self::Satellite),
+            _ => Err(()),
+        }
+    }
 }
 
 /// Define the damages that a weapon can do
@@ -218,6 +719,74 @@ pub struct Damages {
     /// The damages that the weapon can do to a satellite
     #[serde(default)]
     pub satellite: f32,
+    /// How the damages are split between the damage types, kinetic by default
+    #[serde(default)]
+    pub types: DamageTypes,
+}
+
+impl Damages {
+    /// Get the damages that the weapon can do to a kind of target
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use weapons::{Damages, TargetKind};
+    ///
+    /// let damages = Damages {
+    ///     tank: 7.0,
+    ///     ..Default::default()
+    /// };
+    /// assert_eq!(damages.against(TargetKind::Tank), 7.0);
+    /// assert_eq!(damages.against(TargetKind::Ship), 0.0);
+    /// ```
+    pub fn against(&self, target: TargetKind) -> f32 {
+        match target {
+            TargetKind::Building => self.building,
+            TargetKind::Infantry => self.infantry,
+            TargetKind::Vehicle => self.vehicle,
+            TargetKind::ArmoredVehicle => self.armored_vehicle,
+            TargetKind::Tank => self.tank,
+            TargetKind::Helicopter => self.helicopter,
+            TargetKind::Plane => self.plane,
+            TargetKind::Ship => self.ship,
+            TargetKind::Submarine => self.submarine,
+            TargetKind::Missile => self.missile,
+            TargetKind::Satellite => self.satellite,
+        }
+    }
+
+    /// Get the damages multiplied by a factor, the damage types are kept
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use weapons::Damages;
+    ///
+    /// let damages = Damages {
+    ///     building: 10.0,
+    ///     ship: 4.0,
+    ///     ..Default::default()
+    /// };
+    /// let halved = damages.scaled(0.5);
+    /// assert_eq!(halved.building, 5.0);
+    /// assert_eq!(halved.ship, 2.0);
+    /// ```
+    pub fn scaled(&self, factor: f32) -> Damages {
+        Damages {
+            building: self.building * factor,
+            infantry: self.infantry * factor,
+            vehicle: self.vehicle * factor,
+            armored_vehicle: self.armored_vehicle * factor,
+            tank: self.tank * factor,
+            helicopter: self.helicopter * factor,
+            plane: self.plane * factor,
+            ship: self.ship * factor,
+            submarine: self.submarine * factor,
+            missile: self.missile * factor,
+            satellite: self.satellite * factor,
+            types: self.types,
+        }
+    }
 }
 
 /// Define the information that a weapon can have
@@ -234,7 +803,8 @@ pub struct Damages {
 ///   caliber: 5.56,
 ///   speed: 900.0,
 ///   range: 500.0,
-///   country_reference: "fr".into()
+///   country_reference: Some("fr".parse().unwrap()),
+///   ..Default::default()
 /// };
 /// ```
 #[derive(Clone, Default, Debug, Serialize, Deserialize, PartialEq, PartialOrd)]
@@ -249,8 +819,135 @@ pub struct WeaponInformations {
     /// The range in kilometers
     #[serde(default)]
     pub range: f32,
+    /// The thickness of armor in millimeters the projectile can go through
+    #[serde(default)]
+    pub penetration: f32,
     /// The country reference of the weapon, it's used to know which country can use the weapon
+    #[serde(default)]
+    pub country_reference: Option<CountryId>,
+    /// The resources needed to produce one unit of the weapon
+    #[serde(default)]
+    pub cost: Cost,
+    /// What a nation needs to research before producing the weapon
+    #[serde(default)]
+    pub requirements: Requirements,
+    /// How much it costs to keep the weapon in a good condition
+    #[serde(default)]
+    pub maintenance: Maintenance,
+    /// The number of rounds fired per minute
+    #[serde(default)]
+    pub fire_rate: f32,
+    /// The time needed to reload the weapon in seconds
+    #[serde(default)]
+    pub reload_time: f32,
+    /// The number of rounds in a magazine, or of missiles in a salvo
+    ///
+    /// If it's 0, the weapon never needs to be reloaded
+    #[serde(default)]
+    pub magazine_size: u32,
+    /// The number of rounds fired each time the trigger is pulled
+    #[serde(default)]
+    pub burst_length: u32,
+    /// How precise the weapon is
+    #[serde(default)]
+    pub accuracy: Accuracy,
+}
+
+impl WeaponInformations {
+    /// Get the fire rate
+    pub fn get_fire_rate(&self) -> f32 {
+        self.fire_rate
+    }
+
+    /// Get the reload time in seconds
+    pub fn get_reload_time(&self) -> f32 {
+        self.reload_time
+    }
+
+    /// Get the number of rounds in a magazine
+    pub fn get_magazine_size(&self) -> u32 {
+        self.magazine_size
+    }
+
+    /// Get the number of rounds fired in a burst
+    pub fn get_burst_length(&self) -> u32 {
+        self.burst_length
+    }
+
+    /// Get the time in seconds needed to empty a full magazine
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use weapons::WeaponInformations;
+    ///
+    /// let famas = WeaponInformations {
+    ///     fire_rate: 900.0,
+    ///     magazine_size: 30,
+    ///     ..Default::default()
+    /// };
+    /// assert_eq!(famas.magazine_duration(), 2.0);
+    /// ```
+    pub fn magazine_duration(&self) -> f32 {
+        if self.fire_rate <= 0.0 {
+            return 0.0;
+        }
+        self.magazine_size as f32 * 60.0 / self.fire_rate
+    }
+
+    /// Get the number of rounds fired per minute, including the time spent reloading
     ///
-    /// TODO Use a custom type instead of a String
-    pub country_reference: String,
+    /// # Example
+    ///
+    /// ```
+    /// use weapons::WeaponInformations;
+    ///
+    /// let famas = WeaponInformations {
+    ///     fire_rate: 900.0,
+    ///     reload_time: 2.0,
+    ///     magazine_size: 30,
+    ///     ..Default::default()
+    /// };
+    /// // 30 rounds every 4 seconds
+    /// assert_eq!(famas.sustained_fire_rate(), 450.0);
+    ///
+    /// let belt_fed = WeaponInformations {
+    ///     fire_rate: 600.0,
+    ///     reload_time: 10.0,
+    ///     ..Default::default()
+    /// };
+    /// assert_eq!(belt_fed.sustained_fire_rate(), 600.0);
+    /// ```
+    pub fn sustained_fire_rate(&self) -> f32 {
+        if self.magazine_size == 0 || self.fire_rate <= 0.0 {
+            return self.fire_rate.max(0.0);
+        }
+
+        let cycle = self.magazine_duration() + self.reload_time.max(0.0);
+        self.magazine_size as f32 * 60.0 / cycle
+    }
+}
+
+/// Deserialize an enum either from its name or from its numeric id
+///
+/// Configuration files use the numeric ids (see the `TryFrom<i64>` implementations), while
+/// serialized stores use the names.
+pub(crate) fn deserialize_id<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de> + TryFrom<i64>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum IdOrName<T> {
+        Id(i64),
+        Name(T),
+    }
+
+    match IdOrName::<T>::deserialize(deserializer)? {
+        IdOrName::Id(id) => {
+            T::try_from(id).map_err(|_| serde::de::Error::custom(format!("unknown id {id}")))
+        }
+        IdOrName::Name(value) => Ok(value),
+    }
 }
@@ -0,0 +1,333 @@
+//! This module load weapons from configuration files
+//!
+//! A configuration directory contains one file per weapon category, named after the category
+//! (`missiles`, `torpedoes`, `shells`, `firearms`, `bullets`, `bombs` or `drones`) with a `toml`
+//! or `json` extension.
+//! Each file is a table of weapons indexed by their id.
+//!
+//! ```toml
+//! [m51]
+//! guidance = 3 # GPS
+//! projectile = 1 # Ballistic
+//! # ...
+//! ```
+
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::de::DeserializeOwned;
+
+use crate::validation::{Violation, ViolationKind};
+use crate::{WeaponID, WeaponStore};
+
+/// The reason why a configuration file couldn't be loaded
+#[derive(Debug)]
+pub enum LoadErrorKind {
+    /// The file or the directory couldn't be read
+    Io(std::io::Error),
+    /// The TOML file is invalid
+    Toml(toml::de::Error),
+    /// The JSON file is invalid
+    Json(serde_json::Error),
+    /// The name of the file doesn't match any weapon category
+    UnknownCategory,
+    /// A loaded weapon is invalid, see [WeaponStore::validate]
+    Invalid(Violation),
+}
+
+/// An error that occurred while loading a configuration file
+#[derive(Debug)]
+pub struct LoadError {
+    /// The file that couldn't be loaded
+    pub path: PathBuf,
+    pub kind: LoadErrorKind,
+}
+
+impl Display for LoadError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match &self.kind {
+            LoadErrorKind::Io(e) => write!(f, "{}: {}", self.path.display(), e),
+            LoadErrorKind::Toml(e) => write!(f, "{}: {}", self.path.display(), e),
+            LoadErrorKind::Json(e) => write!(f, "{}: {}", self.path.display(), e),
+            LoadErrorKind::UnknownCategory => {
+                write!(f, "{}: unknown weapon category", self.path.display())
+            }
+            LoadErrorKind::Invalid(v) => write!(f, "{}: {}", self.path.display(), v),
+        }
+    }
+}
+
+impl std::error::Error for LoadError {}
+
+/// The formats that can be used in configuration files
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum Format {
+    Toml,
+    Json,
+}
+
+impl Format {
+    pub(crate) fn from_path(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()? {
+            "toml" => Some(Self::Toml),
+            "json" => Some(Self::Json),
+            _ => None,
+        }
+    }
+}
+
+/// Parse a configuration file into a table of weapons
+fn parse<T: DeserializeOwned>(
+    content: &str,
+    format: Format,
+) -> Result<HashMap<WeaponID, T>, LoadErrorKind> {
+    match format {
+        Format::Toml => toml::from_str(content).map_err(LoadErrorKind::Toml),
+        Format::Json => serde_json::from_str(content).map_err(LoadErrorKind::Json),
+    }
+}
+
+impl WeaponStore {
+    /// Load every weapon defined in a configuration directory
+    ///
+    /// Files that aren't `toml` or `json` files are ignored. Every file is loaded even if some
+    /// of them are invalid, and all the errors are returned together. An id already defined by a
+    /// previous file is reported as a [ViolationKind::DuplicateId] of the file defining it again.
+    ///
+    /// Once every file is loaded, the store is validated and each violation is returned as an
+    /// error for the directory.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use weapons::WeaponStore;
+    ///
+    /// let store = WeaponStore::load_from_dir("data/config/weapons").unwrap();
+    /// assert!(store.get_missile("m51").is_some());
+    /// ```
+    pub fn load_from_dir(path: impl AsRef<Path>) -> Result<Self, Vec<LoadError>> {
+        let path = path.as_ref();
+        let entries = fs::read_dir(path).map_err(|e| {
+            vec![LoadError {
+                path: path.to_path_buf(),
+                kind: LoadErrorKind::Io(e),
+            }]
+        })?;
+
+        let mut files = entries
+            .filter_map(|e| e.ok().map(|e| e.path()))
+            .filter(|p| p.is_file())
+            .collect::<Vec<PathBuf>>();
+        // sort the files to always apply them in the same order
+        files.sort();
+
+        let mut store = WeaponStore::default();
+        let mut errors = Vec::new();
+
+        for file in files {
+            let Some(format) = Format::from_path(&file) else {
+                continue;
+            };
+
+            match store.load_file(&file, format) {
+                Ok(violations) => errors.extend(violations.into_iter().map(|v| LoadError {
+                    path: file.clone(),
+                    kind: LoadErrorKind::Invalid(v),
+                })),
+                Err(kind) => errors.push(LoadError { path: file, kind }),
+            }
+        }
+
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+
+        let violations = store.validate();
+        if violations.is_empty() {
+            Ok(store)
+        } else {
+            Err(violations
+                .into_iter()
+                .map(|v| LoadError {
+                    path: path.to_path_buf(),
+                    kind: LoadErrorKind::Invalid(v),
+                })
+                .collect())
+        }
+    }
+
+    /// Load a single configuration file into the store
+    ///
+    /// The weapons whose id is already used in the store, by this category or another one, are
+    /// not loaded and are returned as [ViolationKind::DuplicateId].
+    fn load_file(&mut self, path: &Path, format: Format) -> Result<Vec<Violation>, LoadErrorKind> {
+        let category = path
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .ok_or(LoadErrorKind::UnknownCategory)?;
+
+        let content = fs::read_to_string(path).map_err(LoadErrorKind::Io)?;
+
+        match category {
+            "missiles" => self.load_category(&content, format, |s| &mut s.missiles),
+            "torpedoes" => self.load_category(&content, format, |s| &mut s.torpedoes),
+            "shells" => self.load_category(&content, format, |s| &mut s.shells),
+            "firearms" => self.load_category(&content, format, |s| &mut s.firearm),
+            "bullets" => self.load_category(&content, format, |s| &mut s.bullets),
+            "bombs" => self.load_category(&content, format, |s| &mut s.bombs),
+            "drones" => self.load_category(&content, format, |s| &mut s.drones),
+            _ => Err(LoadErrorKind::UnknownCategory),
+        }
+    }
+
+    /// Parse a configuration file and add its weapons to a category of the store
+    fn load_category<T: DeserializeOwned>(
+        &mut self,
+        content: &str,
+        format: Format,
+        category: impl FnOnce(&mut Self) -> &mut HashMap<WeaponID, T>,
+    ) -> Result<Vec<Violation>, LoadErrorKind> {
+        let mut weapons: HashMap<WeaponID, T> = parse(content, format)?;
+
+        let mut violations: Vec<Violation> = weapons
+            .keys()
+            .filter(|id| self.contains(id.as_str()))
+            .map(|id| Violation {
+                id: id.clone(),
+                kind: ViolationKind::DuplicateId,
+            })
+            .collect();
+        violations.sort();
+        for violation in &violations {
+            weapons.remove(&violation.id);
+        }
+
+        category(self).extend(weapons);
+        Ok(violations)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    fn temp_dir(name: &str) -> std::path::PathBuf {
+        let mut p = std::env::temp_dir();
+        p.push(format!("aegis-weapons-{}-{}", name, uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&p).unwrap();
+        p
+    }
+
+    #[test]
+    fn test_load_data_config() {
+        use super::*;
+
+        let mut p = std::env::current_dir().unwrap();
+        p.push("../../data/config/weapons");
+
+        let store = WeaponStore::load_from_dir(p).unwrap();
+
+        let m51 = store.get_missile("m51").unwrap();
+        assert_eq!(m51.get_informations().name, "M51");
+        assert_eq!(m51.get_warhead_count(), 10);
+
+        let shard = store.get_shell("shard").unwrap();
+        assert_eq!(shard.get_informations().caliber, 155.0);
+    }
+
+    #[test]
+    fn test_load_json() {
+        use super::*;
+
+        let dir = temp_dir("json");
+        fs::write(
+            dir.join("bullets.json"),
+            r#"{ "556": { "bullet_type": "Tracing", "informations": { "name": "5.56", "country_reference": "fr" }, "damages": {} } }"#,
+        )
+        .unwrap();
+
+        let store = WeaponStore::load_from_dir(&dir).unwrap();
+        assert_eq!(
+            store.get_bullet("556").unwrap().get_informations().name,
+            "5.56"
+        );
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_report_every_invalid_file() {
+        use super::*;
+
+        let dir = temp_dir("invalid");
+        fs::write(dir.join("missiles.toml"), "[m51]\nguidance = 42").unwrap();
+        fs::write(dir.join("planes.toml"), "").unwrap();
+        fs::write(dir.join("shells.json"), "{").unwrap();
+        fs::write(dir.join("README.md"), "not a configuration file").unwrap();
+
+        let errors = WeaponStore::load_from_dir(&dir).unwrap_err();
+        assert_eq!(errors.len(), 3);
+        assert!(matches!(errors[0].kind, LoadErrorKind::Toml(_)));
+        assert!(matches!(errors[1].kind, LoadErrorKind::UnknownCategory));
+        assert!(matches!(errors[2].kind, LoadErrorKind::Json(_)));
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_validate_after_loading() {
+        use super::*;
+
+        let dir = temp_dir("validate");
+        fs::write(
+            dir.join("firearms.toml"),
+            "[famas]\nfire_arm_type = 3\nallowed_bullets = []\ndefault_bullets = \"5.56\"\n\
+             [famas.informations]\nname = \"Famas\"\ncountry_reference = \"fr\"\n\
+             [famas.damages]",
+        )
+        .unwrap();
+
+        let errors = WeaponStore::load_from_dir(&dir).unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].path, dir);
+        assert!(matches!(errors[0].kind, LoadErrorKind::Invalid(_)));
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_report_duplicate_ids() {
+        use super::*;
+
+        let dir = temp_dir("duplicate");
+        let informations = "informations = { name = \"x\", country_reference = \"fr\" }";
+        fs::write(
+            dir.join("bullets.json"),
+            r#"{ "x": { "bullet_type": 0, "informations": { "name": "x", "country_reference": "fr" }, "damages": {} } }"#,
+        )
+        .unwrap();
+        fs::write(
+            dir.join("bullets.toml"),
+            format!("[x]\nbullet_type = 0\ndamages = {{}}\n{}", informations),
+        )
+        .unwrap();
+        fs::write(
+            dir.join("shells.toml"),
+            format!("[x]\nshell_type = 0\ndamages = {{}}\n{}", informations),
+        )
+        .unwrap();
+
+        let errors = WeaponStore::load_from_dir(&dir).unwrap_err();
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors[0].path, dir.join("bullets.toml"));
+        assert_eq!(errors[1].path, dir.join("shells.toml"));
+        for error in &errors {
+            assert!(matches!(
+                &error.kind,
+                LoadErrorKind::Invalid(v) if v.id == "x" && v.kind == ViolationKind::DuplicateId
+            ));
+        }
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,333 @@
+//! This module upgrade weapon stores saved by older versions of the server
+//!
+//! The version of a store is serialized with it. Stores saved before the version was added have
+//! the version 0. A store is read as an untyped JSON value and upgraded with [migrate] before
+//! it's converted to a [WeaponStore], so a migration can rename, remove or retype fields of the
+//! older layouts. The deserialization fails if the store was saved by a newer version of the
+//! server.
+//!
+//! To change the format of the store, add a migration at the end of [MIGRATIONS]. New fields
+//! with a `#[serde(default)]` don't need any migration.
+
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+
+use crate::bombs::Bomb;
+use crate::bullets::Bullet;
+use crate::drones::Drone;
+use crate::firearm::FireArm;
+use crate::missiles::Missile;
+use crate::shells::Shell;
+use crate::torpedo::Torpedo;
+use crate::{WeaponID, WeaponStore};
+use serde::Deserialize;
+use serde_json::{Map, Value};
+
+/// A function upgrading a store, as written in a save, to the next version
+pub type Migration = fn(&mut Value);
+
+/// Every migration, the migration at the index `i` upgrades a store from the version `i` to `i + 1`
+pub const MIGRATIONS: &[Migration] = &[
+    // 0 -> 1: the bombs and the drones were added before the version, they may be missing
+    |store| {
+        if let Some(store) = store.as_object_mut() {
+            for category in ["bombs", "drones"] {
+                store
+                    .entry(category)
+                    .or_insert_with(|| Value::Object(Map::new()));
+            }
+        }
+    },
+];
+
+/// The version of the stores saved by this server
+pub const CURRENT_VERSION: u32 = MIGRATIONS.len() as u32;
+
+/// Error returned when a store was saved by a newer version of the server
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UnsupportedVersion {
+    /// The version of the store
+    pub found: u32,
+    /// The latest version supported
+    pub supported: u32,
+}
+
+impl Display for UnsupportedVersion {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "weapon store version {} is newer than the supported version {}",
+            self.found, self.supported
+        )
+    }
+}
+
+impl std::error::Error for UnsupportedVersion {}
+
+/// Error returned when a saved store can't be upgraded
+#[derive(Debug)]
+pub enum MigrationError {
+    /// The store was saved by a newer version of the server
+    UnsupportedVersion(UnsupportedVersion),
+    /// The store doesn't match the layout of its version
+    Invalid(serde_json::Error),
+}
+
+impl Display for MigrationError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnsupportedVersion(e) => write!(f, "{e}"),
+            Self::Invalid(e) => write!(f, "invalid weapon store: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for MigrationError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::UnsupportedVersion(e) => Some(e),
+            Self::Invalid(e) => Some(e),
+        }
+    }
+}
+
+impl From<UnsupportedVersion> for MigrationError {
+    fn from(value: UnsupportedVersion) -> Self {
+        Self::UnsupportedVersion(value)
+    }
+}
+
+/// Upgrade a store, as written in a save, to the current version
+///
+/// The store isn't modified if it was saved by a newer version of the server.
+///
+/// # Example
+///
+/// ```
+/// use weapons::migration::{migrate, CURRENT_VERSION};
+///
+/// let mut store = serde_json::json!({ "missiles": {} });
+/// migrate(&mut store).unwrap();
+/// assert_eq!(store["version"], CURRENT_VERSION);
+/// assert!(store["bombs"].is_object());
+/// ```
+pub fn migrate(store: &mut Value) -> Result<(), MigrationError> {
+    upgrade(store, MIGRATIONS)
+}
+
+fn upgrade(store: &mut Value, migrations: &[Migration]) -> Result<(), MigrationError> {
+    let version = match store.get("version") {
+        Some(version) => u32::deserialize(version).map_err(MigrationError::Invalid)?,
+        None => 0,
+    };
+    let supported = migrations.len() as u32;
+    if version > supported {
+        return Err(UnsupportedVersion {
+            found: version,
+            supported,
+        }
+        .into());
+    }
+
+    for migration in &migrations[version as usize..] {
+        migration(store);
+    }
+    if let Some(store) = store.as_object_mut() {
+        store.insert("version".to_string(), supported.into());
+    }
+    Ok(())
+}
+
+/// A store in the layout of the current version
+#[derive(Deserialize)]
+struct CurrentWeaponStore {
+    version: u32,
+    missiles: HashMap<WeaponID, Missile>,
+    torpedoes: HashMap<WeaponID, Torpedo>,
+    shells: HashMap<WeaponID, Shell>,
+    firearm: HashMap<WeaponID, FireArm>,
+    bullets: HashMap<WeaponID, Bullet>,
+    bombs: HashMap<WeaponID, Bomb>,
+    drones: HashMap<WeaponID, Drone>,
+}
+
+impl TryFrom<Value> for WeaponStore {
+    type Error = MigrationError;
+
+    fn try_from(mut value: Value) -> Result<Self, Self::Error> {
+        migrate(&mut value)?;
+        let store: CurrentWeaponStore =
+            serde_json::from_value(value).map_err(MigrationError::Invalid)?;
+        Ok(WeaponStore {
+            version: store.version,
+            missiles: store.missiles,
+            torpedoes: store.torpedoes,
+            shells: store.shells,
+            firearm: store.firearm,
+            bullets: store.bullets,
+            bombs: store.bombs,
+            drones: store.drones,
+        })
+    }
+}
+
+impl WeaponStore {
+    /// Get the version of the store
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use weapons::migration::CURRENT_VERSION;
+    /// use weapons::WeaponStore;
+    ///
+    /// assert_eq!(WeaponStore::default().get_version(), CURRENT_VERSION);
+    /// ```
+    pub fn get_version(&self) -> u32 {
+        self.version
+    }
+}
+
+#[cfg(test)]
+mod test {
+    #[test]
+    fn test_migrate_unversioned_store() {
+        use super::*;
+
+        let store: WeaponStore = serde_json::from_str(
+            r#"{ "missiles": {}, "torpedoes": {}, "shells": {}, "firearm": {}, "bullets": {} }"#,
+        )
+        .unwrap();
+        assert_eq!(store.get_version(), CURRENT_VERSION);
+    }
+
+    #[test]
+    fn test_migrate_store_with_weapons() {
+        use super::*;
+        use crate::missiles::{MissileGuidanceType, ProjectileType, WarheadCharge};
+
+        let store: WeaponStore = serde_json::from_str(
+            r#"{
+                "missiles": {
+                    "m51": {
+                        "guidance": 3,
+                        "projectile": "Ballistic",
+                        "hypersonic": false,
+                        "warhead": 6,
+                        "warhead_charge": 2,
+                        "warhead_count": 10,
+                        "informations": { "name": "M51", "country_reference": "fr" },
+                        "damages": { "building": 100 }
+                    }
+                },
+                "torpedoes": {},
+                "shells": {},
+                "firearm": {},
+                "bullets": {
+                    "556": {
+                        "bullet_type": 0,
+                        "informations": { "name": "5.56", "country_reference": "fr" },
+                        "damages": {}
+                    }
+                }
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(store.get_version(), CURRENT_VERSION);
+        assert_eq!(store.len(), 2);
+
+        let m51 = store.get_missile("m51").unwrap();
+        assert_eq!(m51.get_missile_type(), MissileGuidanceType::Gps);
+        assert_eq!(m51.get_projectile_type(), ProjectileType::Ballistic);
+        assert_eq!(m51.get_warhead_charge(), WarheadCharge::Nuclear);
+
+        let json = serde_json::to_string(&store).unwrap();
+        let reloaded: WeaponStore = serde_json::from_str(&json).unwrap();
+        assert_eq!(reloaded.get_missile("m51"), Some(m51));
+        assert!(reloaded.get_bullet("556").is_some());
+    }
+
+    #[test]
+    fn test_reject_newer_saved_store() {
+        use super::*;
+
+        let json = format!(
+            r#"{{ "version": {}, "missiles": {{}}, "torpedoes": {{}}, "shells": {{}}, "firearm": {{}}, "bullets": {{}} }}"#,
+            CURRENT_VERSION + 1
+        );
+        let error = serde_json::from_str::<WeaponStore>(&json).unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("is newer than the supported version"));
+    }
+
+    #[test]
+    fn test_version_is_serialized() {
+        use super::*;
+
+        let json = serde_json::to_string(&WeaponStore::default()).unwrap();
+        let store: WeaponStore = serde_json::from_str(&json).unwrap();
+        assert_eq!(store.get_version(), CURRENT_VERSION);
+    }
+
+    #[test]
+    fn test_reject_newer_store() {
+        use super::*;
+
+        let mut store = serde_json::json!({ "version": CURRENT_VERSION + 1 });
+        let error = migrate(&mut store).unwrap_err();
+        assert!(matches!(
+            error,
+            MigrationError::UnsupportedVersion(UnsupportedVersion { found, supported })
+                if found == CURRENT_VERSION + 1 && supported == CURRENT_VERSION
+        ));
+        assert_eq!(store["version"], CURRENT_VERSION + 1);
+    }
+
+    #[test]
+    fn test_migrate_renamed_map() {
+        use super::*;
+
+        // A layout where the bullets were saved as "ammunitions" before the version 1
+        let migrations: &[Migration] = &[|store| {
+            if let Some(store) = store.as_object_mut() {
+                if let Some(bullets) = store.remove("ammunitions") {
+                    store.insert("bullets".to_string(), bullets);
+                }
+            }
+        }];
+        let mut store = serde_json::json!({
+            "missiles": {},
+            "torpedoes": {},
+            "shells": {},
+            "firearm": {},
+            "bombs": {},
+            "drones": {},
+            "ammunitions": {
+                "556": {
+                    "bullet_type": 0,
+                    "informations": { "name": "5.56", "country_reference": "fr" },
+                    "damages": {}
+                }
+            }
+        });
+        upgrade(&mut store, migrations).unwrap();
+        assert_eq!(store["version"], 1);
+        assert!(store.get("ammunitions").is_none());
+
+        let store = WeaponStore::try_from(store).unwrap();
+        assert!(store.get_bullet("556").is_some());
+    }
+
+    #[test]
+    fn test_migrate_missing_categories() {
+        use super::*;
+
+        let v1 = r#"{ "version": 1, "missiles": {}, "torpedoes": {}, "shells": {}, "firearm": {}, "bullets": {} }"#;
+        assert!(serde_json::from_str::<WeaponStore>(v1).is_err());
+
+        let v0 =
+            r#"{ "missiles": {}, "torpedoes": {}, "shells": {}, "firearm": {}, "bullets": {} }"#;
+        let store: WeaponStore = serde_json::from_str(v0).unwrap();
+        assert!(store.is_empty());
+    }
+}
@@ -1,6 +1,6 @@
 //! This module define missiles
 
-use crate::{Damages, Speed, WeaponInformations};
+use crate::{Damages, Speed, Weapon, WeaponCategory, WeaponInformations};
 use serde::{Deserialize, Serialize};
 
 /// The projectile type is the type of trajectory the missile will be using
@@ -137,27 +137,97 @@ impl TryFrom<i64> for WarheadCharge {
 /// The warhead count is the number of warhead that is used in the missile
 pub type WarheadCount = u32;
 
+/// Blast radius in kilometers of a one kiloton warhead, it grows with the cube root of the yield
+pub const BLAST_RADIUS_PER_KILOTON: f32 = 0.6;
+
+/// Define the effects of a warhead around its impact point
+#[derive(Clone, Default, Copy, Debug, Serialize, Deserialize, PartialEq, PartialOrd)]
+pub struct AreaEffect {
+    /// The yield of each warhead in kilotons of TNT
+    #[serde(default)]
+    pub kilotons: f32,
+    /// The radius in kilometers where the damages are fully applied
+    ///
+    /// If it's 0, it's computed from the yield
+    #[serde(default)]
+    pub blast_radius: f32,
+    /// How long the area stays contaminated after the impact, in hours
+    ///
+    /// It's used by nuclear, chemical and biological charges
+    #[serde(default)]
+    pub fallout_duration: f32,
+}
+
+impl AreaEffect {
+    /// Get the radius in kilometers where the damages are fully applied
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use weapons::missiles::AreaEffect;
+    ///
+    /// let nuclear = AreaEffect {
+    ///     kilotons: 1000.0,
+    ///     ..Default::default()
+    /// };
+    /// assert_eq!(nuclear.get_blast_radius(), 6.0);
+    /// ```
+    pub fn get_blast_radius(&self) -> f32 {
+        if self.blast_radius > 0.0 {
+            return self.blast_radius;
+        }
+        BLAST_RADIUS_PER_KILOTON * self.kilotons.max(0.0).cbrt()
+    }
+
+    /// Get the part (from 0.0 to 1.0) of the damages applied at a distance in kilometers
+    ///
+    /// The damages are fully applied inside the blast radius, then decrease with the cube of the
+    /// distance, like the overpressure of an explosion.
+    pub fn falloff(&self, distance_from_impact: f32) -> f32 {
+        let distance = distance_from_impact.max(0.0);
+        let radius = self.get_blast_radius();
+        if distance <= radius {
+            return 1.0;
+        }
+        (radius / distance).powi(3)
+    }
+
+    /// Return a boolean that indicates if the area is still contaminated some hours after impact
+    ///
+    /// The charge of the warhead isn't known here, see [Missile::is_contaminated]
+    pub fn is_contaminated(&self, hours_since_impact: f32) -> bool {
+        hours_since_impact < self.fallout_duration
+    }
+}
+
 /// Represents a missile
 ///
 /// This instance can be used in two ways:
 /// - Represent a missile that is fired by a unit
 /// - Represent a missile for its information, such as in the research tree
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct Missile {
     /// The guidance type of the missile
+    #[serde(deserialize_with = "crate::deserialize_id")]
     guidance: MissileGuidanceType,
     /// The type of projectile that is used in the missile
+    #[serde(deserialize_with = "crate::deserialize_id")]
     projectile: ProjectileType,
     /// If the missile is hypersonic, it means that he is able to go faster than Mach 5 and can
     /// dodge anti-missile systems more easily
     hypersonic: bool,
 
     /// The type of warhead that is used in the missile
+    #[serde(deserialize_with = "crate::deserialize_id")]
     warhead: WarheadType,
     /// The type of explosive charge that is used in the warhead
+    #[serde(deserialize_with = "crate::deserialize_id")]
     warhead_charge: WarheadCharge,
     /// The number of warhead that is used in the missile
     warhead_count: WarheadCount,
+    /// The effects of each warhead around its impact point
+    #[serde(default)]
+    area_effect: AreaEffect,
 
     /// The information about the missile
     informations: WeaponInformations,
@@ -190,6 +260,7 @@ impl Missile {
             warhead: WarheadType::Cruise,
             warhead_charge: WarheadCharge::Standard,
             warhead_count: 1,
+            area_effect: AreaEffect::default(),
             informations: WeaponInformations::default(),
             damages: Damages::default(),
         }
@@ -405,6 +476,85 @@ impl Missile {
         self.warhead_count = warhead_count;
     }
 
+    /// Get the effects of each warhead around its impact point
+    pub fn get_area_effect(&self) -> &AreaEffect {
+        &self.area_effect
+    }
+
+    /// Get the effects of each warhead around its impact point with a mutable reference
+    pub fn get_area_effect_mut(&mut self) -> &mut AreaEffect {
+        &mut self.area_effect
+    }
+
+    /// Set the effects of each warhead around its impact point
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use weapons::missiles::{AreaEffect, Missile, MissileGuidanceType, ProjectileType};
+    ///
+    /// let mut missile = Missile::new(MissileGuidanceType::Gps, ProjectileType::Ballistic);
+    /// missile.set_area_effect(AreaEffect {
+    ///     kilotons: 100.0,
+    ///     blast_radius: 3.0,
+    ///     fallout_duration: 48.0,
+    /// });
+    /// assert_eq!(missile.get_area_effect().kilotons, 100.0);
+    /// ```
+    pub fn set_area_effect(&mut self, area_effect: AreaEffect) {
+        self.area_effect = area_effect;
+    }
+
+    /// Get the damages of one warhead at a distance in kilometers from its impact point
+    ///
+    /// See [AreaEffect::falloff]
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use weapons::missiles::{AreaEffect, Missile, MissileGuidanceType, ProjectileType};
+    ///
+    /// let mut missile = Missile::new(MissileGuidanceType::Gps, ProjectileType::Ballistic);
+    /// missile.get_damages_mut().building = 100.0;
+    /// missile.set_area_effect(AreaEffect {
+    ///     blast_radius: 2.0,
+    ///     ..Default::default()
+    /// });
+    ///
+    /// assert_eq!(missile.area_damages(1.0).building, 100.0);
+    /// assert_eq!(missile.area_damages(4.0).building, 12.5);
+    /// ```
+    pub fn area_damages(&self, distance_from_impact: f32) -> Damages {
+        self.damages
+            .scaled(self.area_effect.falloff(distance_from_impact))
+    }
+
+    /// Return a boolean that indicates if the impact area is still contaminated some hours after
+    /// impact
+    ///
+    /// Only nuclear, chemical and biological charges contaminate the area, see
+    /// [AreaEffect::is_contaminated]
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use weapons::missiles::{AreaEffect, Missile, MissileGuidanceType, ProjectileType, WarheadCharge};
+    ///
+    /// let mut missile = Missile::new(MissileGuidanceType::Gps, ProjectileType::Ballistic);
+    /// missile.set_area_effect(AreaEffect {
+    ///     fallout_duration: 24.0,
+    ///     ..Default::default()
+    /// });
+    /// assert!(!missile.is_contaminated(12.0));
+    ///
+    /// missile.set_warhead_charge(WarheadCharge::Chemical);
+    /// assert!(missile.is_contaminated(12.0));
+    /// ```
+    pub fn is_contaminated(&self, hours_since_impact: f32) -> bool {
+        self.warhead_charge != WarheadCharge::Standard
+            && self.area_effect.is_contaminated(hours_since_impact)
+    }
+
     /// Get the information about the missile
     ///
     /// # Example
@@ -449,7 +599,8 @@ impl Missile {
     ///   caliber: 0.0,
     ///   speed: 315.0,
     ///   range: 180.0,
-    ///   country_reference: "fr".to_string()
+    ///   country_reference: Some("fr".parse().unwrap()),
+    ///   ..Default::default()
     /// });
     /// assert_eq!(missile.get_informations().name, "Exocet".to_string());
     /// ```
@@ -473,6 +624,28 @@ impl Missile {
     }
 }
 
+impl Weapon for Missile {
+    fn category(&self) -> WeaponCategory {
+        WeaponCategory::Missile
+    }
+
+    fn get_informations(&self) -> &WeaponInformations {
+        &self.informations
+    }
+
+    fn get_informations_mut(&mut self) -> &mut WeaponInformations {
+        &mut self.informations
+    }
+
+    fn get_damages(&self) -> &Damages {
+        &self.damages
+    }
+
+    fn get_damages_mut(&mut self) -> &mut Damages {
+        &mut self.damages
+    }
+}
+
 #[cfg(test)]
 mod test {
     #[test]
@@ -570,7 +743,8 @@ mod test {
             caliber: 0.0,
             speed: 315.0,
             range: 180.0,
-            country_reference: "FR".to_string(),
+            country_reference: Some("FR".parse().unwrap()),
+            ..Default::default()
         });
         assert_eq!(missile.get_informations().name, "Exocet".to_string());
         assert_eq!(missile.get_informations().caliber, 0.0);
@@ -607,6 +781,7 @@ mod test {
             submarine: 9.0,
             missile: 10.0,
             satellite: 11.0,
+            ..Default::default()
         });
 
         assert_eq!(missile.get_damages().building, 1.0);
@@ -621,4 +796,30 @@ mod test {
         assert_eq!(missile.get_damages().missile, 10.0);
         assert_eq!(missile.get_damages().satellite, 11.0);
     }
+
+    #[test]
+    fn test_area_damages() {
+        use super::*;
+
+        let mut missile = Missile::new(MissileGuidanceType::Gps, ProjectileType::Ballistic);
+        missile.get_damages_mut().infantry = 10.0;
+        assert_eq!(missile.area_damages(0.0).infantry, 10.0);
+        assert_eq!(missile.area_damages(0.1).infantry, 0.0);
+
+        missile.set_warhead_charge(WarheadCharge::Nuclear);
+        missile.set_area_effect(AreaEffect {
+            kilotons: 8.0,
+            blast_radius: 0.0,
+            fallout_duration: 24.0,
+        });
+        assert_eq!(missile.get_area_effect().get_blast_radius(), 1.2);
+        assert_eq!(missile.area_damages(1.0).infantry, 10.0);
+        assert!(missile.area_damages(2.0).infantry < 10.0);
+        assert!(missile.area_damages(2.0).infantry > missile.area_damages(3.0).infantry);
+        assert!(missile.is_contaminated(12.0));
+        assert!(!missile.is_contaminated(24.0));
+
+        missile.set_warhead_charge(WarheadCharge::Standard);
+        assert!(!missile.is_contaminated(12.0));
+    }
 }
@@ -0,0 +1,143 @@
+//! This module define what a nation needs to research before producing a weapon
+
+use std::collections::HashSet;
+
+use crate::{WeaponID, WeaponRef, WeaponStore};
+use serde::{Deserialize, Serialize};
+
+/// The id of a node of the technology tree
+pub type TechID = String;
+
+/// Define what is needed to produce a weapon
+#[derive(Clone, Default, Debug, Serialize, Deserialize, PartialEq, PartialOrd)]
+pub struct Requirements {
+    /// The technologies that must be researched
+    #[serde(default)]
+    pub technologies: Vec<TechID>,
+    /// The first year the weapon can be produced
+    #[serde(default)]
+    pub min_year: Option<i32>,
+}
+
+impl Requirements {
+    /// Return a boolean that indicates if a nation meets the requirements
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use weapons::requirements::{Requirements, TechState};
+    ///
+    /// let requirements = Requirements {
+    ///     technologies: vec!["rocketry".to_string()],
+    ///     min_year: Some(1960),
+    /// };
+    ///
+    /// let mut state = TechState::new(1955);
+    /// state.research("rocketry");
+    /// assert!(!requirements.is_met(&state));
+    ///
+    /// state.set_year(1960);
+    /// assert!(requirements.is_met(&state));
+    /// ```
+    pub fn is_met(&self, state: &TechState) -> bool {
+        let year = self.min_year.is_none_or(|year| state.year >= year);
+        year && self
+            .technologies
+            .iter()
+            .all(|technology| state.is_researched(technology))
+    }
+}
+
+/// The technologies researched by a nation and the current year
+#[derive(Clone, Default, Debug, Serialize, Deserialize, PartialEq)]
+pub struct TechState {
+    #[serde(default)]
+    researched: HashSet<TechID>,
+    year: i32,
+}
+
+impl TechState {
+    /// Create a new state without any technology researched
+    pub fn new(year: i32) -> Self {
+        Self {
+            researched: HashSet::new(),
+            year,
+        }
+    }
+
+    /// Mark a technology as researched
+    pub fn research(&mut self, technology: impl Into<TechID>) {
+        self.researched.insert(technology.into());
+    }
+
+    /// Return a boolean that indicates if a technology has been researched
+    pub fn is_researched(&self, technology: &str) -> bool {
+        self.researched.contains(technology)
+    }
+
+    /// Get the current year
+    pub fn get_year(&self) -> i32 {
+        self.year
+    }
+
+    /// Define the current year
+    pub fn set_year(&mut self, year: i32) {
+        self.year = year;
+    }
+}
+
+impl WeaponStore {
+    /// Iterate over the weapons that a nation can produce
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use weapons::requirements::TechState;
+    /// use weapons::shells::{Shell, ShellType};
+    /// use weapons::WeaponStore;
+    ///
+    /// let mut store = WeaponStore::default();
+    /// let mut apfsds = Shell::new(ShellType::ArmorPiercingFinStabilizedDiscardingSabot);
+    /// apfsds.get_informations_mut().requirements.min_year = Some(1975);
+    /// store.insert("apfsds", apfsds);
+    /// store.insert("he", Shell::new(ShellType::HighExplosive));
+    ///
+    /// let state = TechState::new(1960);
+    /// let available: Vec<_> = store.available_for(&state).map(|(id, _)| id).collect();
+    /// assert_eq!(available, vec!["he"]);
+    /// ```
+    pub fn available_for<'a>(
+        &'a self,
+        state: &'a TechState,
+    ) -> impl Iterator<Item = (&'a WeaponID, WeaponRef<'a>)> {
+        self.iter().filter(move |(_, weapon)| {
+            weapon
+                .weapon()
+                .get_informations()
+                .requirements
+                .is_met(state)
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    #[test]
+    fn test_every_technology_is_needed() {
+        use super::*;
+
+        let requirements = Requirements {
+            technologies: vec!["radar".to_string(), "jet_engine".to_string()],
+            min_year: None,
+        };
+        let mut state = TechState::default();
+        assert!(!requirements.is_met(&state));
+
+        state.research("radar");
+        assert!(!requirements.is_met(&state));
+
+        state.research("jet_engine");
+        assert!(requirements.is_met(&state));
+        assert!(Requirements::default().is_met(&TechState::default()));
+    }
+}
@@ -1,6 +1,6 @@
 //! This module define shells used in tanks and armored vehicles.
 
-use crate::{Damages, WeaponInformations};
+use crate::{Damages, Weapon, WeaponCategory, WeaponInformations};
 use serde::{Deserialize, Serialize};
 
 /// The type of shell
@@ -74,6 +74,7 @@ impl TryFrom<i64> for ShellType {
 /// A shell is a projectile that is fired by a tank, a cannon, a howitzer or a mortar
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, PartialOrd)]
 pub struct Shell {
+    #[serde(deserialize_with = "crate::deserialize_id")]
     shell_type: ShellType,
 
     informations: WeaponInformations,
@@ -186,6 +187,28 @@ impl Shell {
     }
 }
 
+impl Weapon for Shell {
+    fn category(&self) -> WeaponCategory {
+        WeaponCategory::Shell
+    }
+
+    fn get_informations(&self) -> &WeaponInformations {
+        &self.informations
+    }
+
+    fn get_informations_mut(&mut self) -> &mut WeaponInformations {
+        &mut self.informations
+    }
+
+    fn get_damages(&self) -> &Damages {
+        &self.damages
+    }
+
+    fn get_damages_mut(&mut self) -> &mut Damages {
+        &mut self.damages
+    }
+}
+
 #[cfg(test)]
 mod test {
     #[test]
@@ -249,15 +272,4 @@ mod test {
         assert_eq!(shell.get_damages().missile, 0.0);
         assert_eq!(shell.get_damages().satellite, 0.0);
     }
-
-    #[cfg(feature = "load_configuration")]
-    #[test]
-    fn load_config() {
-        let mut p = std::env::current_dir().unwrap();
-        p.push("../../data/config/weapons");
-
-        let store = crate::loader::load(p).unwrap();
-
-        dbg!(store);
-    }
 }
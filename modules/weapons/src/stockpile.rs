@@ -0,0 +1,446 @@
+//! This module define the stockpiles of weapons owned by a nation or a unit
+//!
+//! Part of a stock can be reserved, for example for a planned attack. Reserved weapons can only be
+//! consumed with [Stockpile::consume_reserved].
+//!
+//! Each stock tracks the average condition of its weapons, see [crate::condition].
+
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+
+use crate::condition::{Condition, Maintenance};
+use crate::WeaponID;
+use resources::Money;
+use serde::{Deserialize, Serialize};
+
+/// The stock of one weapon
+///
+/// The number of reserved weapons is never greater than the total number of weapons.
+#[derive(Clone, Copy, Default, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(try_from = "RawStock")]
+pub struct Stock {
+    /// The total number of weapons, reserved or not
+    quantity: u64,
+    /// The number of weapons that are reserved
+    reserved: u64,
+    /// The average condition of the weapons
+    condition: Condition,
+}
+
+impl Stock {
+    /// Create a new stock of brand new weapons, return an error if more weapons are reserved than
+    /// in the stock
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use weapons::stockpile::Stock;
+    ///
+    /// let stock = Stock::new(10, 4).unwrap();
+    /// assert_eq!(stock.available(), 6);
+    /// assert!(Stock::new(1, 5).is_err());
+    /// ```
+    pub fn new(quantity: u64, reserved: u64) -> Result<Self, InvalidStock> {
+        if reserved > quantity {
+            return Err(InvalidStock { quantity, reserved });
+        }
+        Ok(Self {
+            quantity,
+            reserved,
+            condition: Condition::default(),
+        })
+    }
+
+    /// Get the total number of weapons, reserved or not
+    pub fn get_quantity(&self) -> u64 {
+        self.quantity
+    }
+
+    /// Get the number of weapons that are reserved
+    pub fn get_reserved(&self) -> u64 {
+        self.reserved
+    }
+
+    /// Get the average condition of the weapons
+    pub fn get_condition(&self) -> Condition {
+        self.condition
+    }
+
+    /// Get the number of weapons that aren't reserved
+    pub fn available(&self) -> u64 {
+        self.quantity - self.reserved
+    }
+}
+
+/// A stock as written in a save, checked before being used
+#[derive(Deserialize)]
+struct RawStock {
+    #[serde(default)]
+    quantity: u64,
+    #[serde(default)]
+    reserved: u64,
+    #[serde(default)]
+    condition: Condition,
+}
+
+impl TryFrom<RawStock> for Stock {
+    type Error = InvalidStock;
+
+    fn try_from(value: RawStock) -> Result<Self, Self::Error> {
+        let mut stock = Self::new(value.quantity, value.reserved)?;
+        stock.condition = value.condition;
+        Ok(stock)
+    }
+}
+
+/// Error returned when more weapons are reserved than in the stock
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InvalidStock {
+    pub quantity: u64,
+    pub reserved: u64,
+}
+
+impl Display for InvalidStock {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} weapons reserved but only {} in stock",
+            self.reserved, self.quantity
+        )
+    }
+}
+
+impl std::error::Error for InvalidStock {}
+
+/// Error returned when there isn't enough weapons in a stockpile
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InsufficientStock {
+    pub id: WeaponID,
+    /// The number of weapons requested
+    pub requested: u64,
+    /// The number of weapons that could be used
+    pub available: u64,
+}
+
+impl Display for InsufficientStock {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}: {} requested but only {} available",
+            self.id, self.requested, self.available
+        )
+    }
+}
+
+impl std::error::Error for InsufficientStock {}
+
+/// Contains the quantity of each weapon
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(transparent)]
+pub struct Stockpile {
+    stocks: HashMap<WeaponID, Stock>,
+}
+
+impl Stockpile {
+    /// Get the stock of a weapon
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use weapons::stockpile::{Stock, Stockpile};
+    ///
+    /// let mut stockpile = Stockpile::default();
+    /// stockpile.add("shard", 10);
+    /// stockpile.reserve("shard", 4).unwrap();
+    /// assert_eq!(stockpile.get("shard"), Stock::new(10, 4).unwrap());
+    /// assert_eq!(stockpile.get("m51"), Stock::default());
+    /// ```
+    pub fn get(&self, id: impl Into<WeaponID>) -> Stock {
+        self.stocks.get(&id.into()).copied().unwrap_or_default()
+    }
+
+    /// Get the total number of a weapon, reserved or not
+    pub fn get_quantity(&self, id: impl Into<WeaponID>) -> u64 {
+        self.get(id).quantity
+    }
+
+    /// Get the number of a weapon that isn't reserved
+    pub fn get_available(&self, id: impl Into<WeaponID>) -> u64 {
+        self.get(id).available()
+    }
+
+    /// Get the number of a weapon that is reserved
+    pub fn get_reserved(&self, id: impl Into<WeaponID>) -> u64 {
+        self.get(id).reserved
+    }
+
+    /// Get the average condition of a weapon
+    pub fn get_condition(&self, id: impl Into<WeaponID>) -> Condition {
+        self.get(id).condition
+    }
+
+    /// Add brand new weapons to the stockpile
+    ///
+    /// The condition of the stock becomes the average of the condition of the weapons already in
+    /// the stock and of the new weapons.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use weapons::stockpile::Stockpile;
+    ///
+    /// let mut stockpile = Stockpile::default();
+    /// stockpile.add("shard", 10);
+    /// stockpile.add("shard", 5);
+    /// assert_eq!(stockpile.get_quantity("shard"), 15);
+    /// ```
+    pub fn add(&mut self, id: impl Into<WeaponID>, quantity: u64) {
+        if quantity == 0 {
+            return;
+        }
+        let stock = self.stocks.entry(id.into()).or_default();
+        let total = stock.quantity.saturating_add(quantity);
+        let condition = (stock.condition.get() * stock.quantity as f32
+            + Condition::default().get() * quantity as f32)
+            / total as f32;
+        stock.quantity = total;
+        stock.condition = Condition::new(condition);
+    }
+
+    /// Apply one tick of maintenance to the stock of a weapon
+    ///
+    /// See [Maintenance::apply]
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use resources::Money;
+    /// use weapons::condition::Maintenance;
+    /// use weapons::stockpile::Stockpile;
+    ///
+    /// let maintenance = Maintenance {
+    ///     cost: Money::new(10),
+    ///     wear: 0.1,
+    /// };
+    /// let mut stockpile = Stockpile::default();
+    /// stockpile.add("shard", 2);
+    ///
+    /// stockpile.maintain("shard", &maintenance, &Money::new(10));
+    /// assert_eq!(stockpile.get_condition("shard").get(), 0.95);
+    ///
+    /// // brand new weapons improve the average condition
+    /// stockpile.add("shard", 2);
+    /// assert_eq!(stockpile.get_condition("shard").get(), 0.975);
+    /// ```
+    pub fn maintain(&mut self, id: impl Into<WeaponID>, maintenance: &Maintenance, spent: &Money) {
+        if let Some(stock) = self.stocks.get_mut(&id.into()) {
+            maintenance.apply(&mut stock.condition, spent, stock.quantity);
+        }
+    }
+
+    /// Remove weapons that aren't reserved from the stockpile
+    ///
+    /// Nothing is removed if there isn't enough weapons available.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use weapons::stockpile::Stockpile;
+    ///
+    /// let mut stockpile = Stockpile::default();
+    /// stockpile.add("shard", 10);
+    /// stockpile.reserve("shard", 8).unwrap();
+    ///
+    /// assert!(stockpile.consume("shard", 3).is_err());
+    /// assert!(stockpile.consume("shard", 2).is_ok());
+    /// assert_eq!(stockpile.get_quantity("shard"), 8);
+    /// ```
+    pub fn consume(
+        &mut self,
+        id: impl Into<WeaponID>,
+        quantity: u64,
+    ) -> Result<(), InsufficientStock> {
+        let id = id.into();
+        let available = self.get_available(id.clone());
+        if available < quantity {
+            return Err(InsufficientStock {
+                id,
+                requested: quantity,
+                available,
+            });
+        }
+        self.update(id, |stock| {
+            stock.quantity = stock.quantity.saturating_sub(quantity)
+        });
+        Ok(())
+    }
+
+    /// Reserve weapons, they can't be consumed by [Self::consume] anymore
+    ///
+    /// Nothing is reserved if there isn't enough weapons available.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use weapons::stockpile::Stockpile;
+    ///
+    /// let mut stockpile = Stockpile::default();
+    /// stockpile.add("m51", 16);
+    ///
+    /// let error = stockpile.reserve("m51", 20).unwrap_err();
+    /// assert_eq!(error.available, 16);
+    ///
+    /// stockpile.reserve("m51", 10).unwrap();
+    /// assert_eq!(stockpile.get_available("m51"), 6);
+    /// ```
+    pub fn reserve(
+        &mut self,
+        id: impl Into<WeaponID>,
+        quantity: u64,
+    ) -> Result<(), InsufficientStock> {
+        let id = id.into();
+        let available = self.get_available(id.clone());
+        if available < quantity {
+            return Err(InsufficientStock {
+                id,
+                requested: quantity,
+                available,
+            });
+        }
+        self.update(id, |stock| {
+            stock.reserved = stock.reserved.saturating_add(quantity)
+        });
+        Ok(())
+    }
+
+    /// Release reserved weapons, so they can be consumed by [Self::consume] again
+    ///
+    /// If fewer weapons are reserved, every reserved weapon is released.
+    pub fn release(&mut self, id: impl Into<WeaponID>, quantity: u64) {
+        self.update(id.into(), |stock| {
+            stock.reserved = stock.reserved.saturating_sub(quantity)
+        });
+    }
+
+    /// Remove reserved weapons from the stockpile
+    ///
+    /// Nothing is removed if there isn't enough weapons reserved.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use weapons::stockpile::Stockpile;
+    ///
+    /// let mut stockpile = Stockpile::default();
+    /// stockpile.add("shard", 10);
+    /// stockpile.reserve("shard", 4).unwrap();
+    ///
+    /// stockpile.consume_reserved("shard", 4).unwrap();
+    /// assert_eq!(stockpile.get_quantity("shard"), 6);
+    /// assert_eq!(stockpile.get_reserved("shard"), 0);
+    /// assert!(stockpile.consume_reserved("shard", 1).is_err());
+    /// ```
+    pub fn consume_reserved(
+        &mut self,
+        id: impl Into<WeaponID>,
+        quantity: u64,
+    ) -> Result<(), InsufficientStock> {
+        let id = id.into();
+        let stock = self.get(id.clone());
+        let (Some(quantity_left), Some(reserved_left)) = (
+            stock.quantity.checked_sub(quantity),
+            stock.reserved.checked_sub(quantity),
+        ) else {
+            return Err(InsufficientStock {
+                id,
+                requested: quantity,
+                available: stock.reserved,
+            });
+        };
+        self.update(id, |stock| {
+            stock.quantity = quantity_left;
+            stock.reserved = reserved_left;
+        });
+        Ok(())
+    }
+
+    /// Iterate over every weapon in the stockpile
+    pub fn iter(&self) -> impl Iterator<Item = (&WeaponID, &Stock)> {
+        self.stocks.iter()
+    }
+
+    /// Return a boolean that indicates if the stockpile is empty
+    pub fn is_empty(&self) -> bool {
+        self.stocks.is_empty()
+    }
+
+    /// Modify the stock of a weapon and forget it once it's empty
+    fn update(&mut self, id: WeaponID, f: impl FnOnce(&mut Stock)) {
+        if let Some(stock) = self.stocks.get_mut(&id) {
+            f(stock);
+            if stock.quantity == 0 {
+                self.stocks.remove(&id);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    #[test]
+    fn test_empty_stocks_are_removed() {
+        use super::*;
+
+        let mut stockpile = Stockpile::default();
+        stockpile.add("shard", 0);
+        assert!(stockpile.is_empty());
+
+        stockpile.add("shard", 2);
+        stockpile.consume("shard", 2).unwrap();
+        assert!(stockpile.is_empty());
+    }
+
+    #[test]
+    fn test_errors_keep_the_stock() {
+        use super::*;
+
+        let mut stockpile = Stockpile::default();
+        stockpile.add("shard", 3);
+
+        assert_eq!(
+            stockpile.consume("shard", 5),
+            Err(InsufficientStock {
+                id: "shard".to_string(),
+                requested: 5,
+                available: 3
+            })
+        );
+        assert!(stockpile.reserve("m51", 1).is_err());
+        assert_eq!(stockpile.get_quantity("shard"), 3);
+
+        stockpile.reserve("shard", 2).unwrap();
+        stockpile.release("shard", 10);
+        assert_eq!(stockpile.get_available("shard"), 3);
+    }
+
+    #[cfg(feature = "config")]
+    #[test]
+    fn test_reject_invalid_stock() {
+        use super::*;
+
+        let error =
+            serde_json::from_str::<Stockpile>(r#"{ "a": { "quantity": 1, "reserved": 5 } }"#)
+                .unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("5 weapons reserved but only 1 in stock"));
+
+        let stockpile: Stockpile =
+            serde_json::from_str(r#"{ "a": { "quantity": 5, "reserved": 1 } }"#).unwrap();
+        assert_eq!(stockpile.get("a"), Stock::new(5, 1).unwrap());
+
+        let stockpile: Stockpile =
+            serde_json::from_str(r#"{ "a": { "quantity": 5, "condition": 0.5 } }"#).unwrap();
+        assert_eq!(stockpile.get_condition("a"), Condition::new(0.5));
+    }
+}
@@ -1,6 +1,6 @@
 //! This crate is used to define torpedoes
 
-use crate::{Damages, WeaponInformations};
+use crate::{Damages, Weapon, WeaponCategory, WeaponInformations};
 use serde::{Deserialize, Serialize};
 
 /// Enumeration representing different types of propulsion for torpedoes.
@@ -59,7 +59,9 @@ impl TryFrom<i64> for GuidanceType {
 /// A torpedo object
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Torpedo {
+    #[serde(deserialize_with = "crate::deserialize_id")]
     guidance: GuidanceType,
+    #[serde(deserialize_with = "crate::deserialize_id")]
     propulsion: PropulsionType,
 
     informations: WeaponInformations,
@@ -192,3 +194,25 @@ impl Torpedo {
         &mut self.damages
     }
 }
+
+impl Weapon for Torpedo {
+    fn category(&self) -> WeaponCategory {
+        WeaponCategory::Torpedo
+    }
+
+    fn get_informations(&self) -> &WeaponInformations {
+        &self.informations
+    }
+
+    fn get_informations_mut(&mut self) -> &mut WeaponInformations {
+        &mut self.informations
+    }
+
+    fn get_damages(&self) -> &Damages {
+        &self.damages
+    }
+
+    fn get_damages_mut(&mut self) -> &mut Damages {
+        &mut self.damages
+    }
+}
@@ -0,0 +1,282 @@
+//! This module check that the weapons of a store are coherent
+//!
+//! Speeds aren't checked, since a negative speed means that the damages are instantly applied.
+
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+
+use crate::country::{CountryId, CountryRegistry};
+use crate::{WeaponID, WeaponInformations, WeaponStore};
+
+/// The kind of problem found on a weapon
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ViolationKind {
+    /// The firearm uses a bullet that doesn't exist in the store
+    UnknownBullet(WeaponID),
+    /// The weapon has no country reference
+    MissingCountry,
+    /// The country of the weapon isn't in the registry
+    UnknownCountry(CountryId),
+    /// The range of the weapon is negative
+    NegativeRange,
+    /// The caliber of the weapon is negative
+    NegativeCaliber,
+    /// The same id is defined several times, in one or several categories
+    DuplicateId,
+}
+
+/// A problem found on a weapon
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Violation {
+    /// The id of the weapon
+    pub id: WeaponID,
+    pub kind: ViolationKind,
+}
+
+impl Display for Violation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match &self.kind {
+            ViolationKind::UnknownBullet(bullet) => {
+                write!(f, "{}: unknown bullet '{}'", self.id, bullet)
+            }
+            ViolationKind::MissingCountry => write!(f, "{}: missing country reference", self.id),
+            ViolationKind::UnknownCountry(country) => {
+                write!(f, "{}: unknown country '{}'", self.id, country)
+            }
+            ViolationKind::NegativeRange => write!(f, "{}: negative range", self.id),
+            ViolationKind::NegativeCaliber => write!(f, "{}: negative caliber", self.id),
+            ViolationKind::DuplicateId => {
+                write!(f, "{}: id defined several times", self.id)
+            }
+        }
+    }
+}
+
+impl WeaponStore {
+    /// Check every weapon of the store and return the problems found, sorted by weapon id
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use weapons::firearm::{FireArm, FireArmType};
+    /// use weapons::validation::{Violation, ViolationKind};
+    /// use weapons::WeaponStore;
+    ///
+    /// let mut store = WeaponStore::default();
+    /// let mut famas = FireArm::new(FireArmType::Assault, "5.56");
+    /// famas.get_informations_mut().country_reference = Some("fr".parse().unwrap());
+    /// store.add_firearm("famas", famas);
+    ///
+    /// assert_eq!(
+    ///     store.validate(),
+    ///     vec![Violation {
+    ///         id: "famas".to_string(),
+    ///         kind: ViolationKind::UnknownBullet("5.56".to_string())
+    ///     }]
+    /// );
+    /// ```
+    pub fn validate(&self) -> Vec<Violation> {
+        let mut violations = Vec::new();
+
+        let informations = self
+            .iter()
+            .map(|(id, w)| (id, w.weapon().get_informations()));
+
+        let mut categories: HashMap<&WeaponID, usize> = HashMap::new();
+        for (id, informations) in informations {
+            *categories.entry(id).or_default() += 1;
+            check_informations(id, informations, &mut violations);
+        }
+
+        violations.extend(
+            categories
+                .into_iter()
+                .filter(|(_, count)| *count > 1)
+                .map(|(id, _)| Violation {
+                    id: id.clone(),
+                    kind: ViolationKind::DuplicateId,
+                }),
+        );
+
+        for (id, firearm) in &self.firearm {
+            let bullets =
+                std::iter::once(firearm.get_default_bullet()).chain(firearm.get_allowed_bullets());
+            for bullet in bullets {
+                if !self.bullets.contains_key(bullet) {
+                    violations.push(Violation {
+                        id: id.clone(),
+                        kind: ViolationKind::UnknownBullet(bullet.clone()),
+                    });
+                }
+            }
+        }
+
+        violations.sort();
+        violations.dedup();
+        violations
+    }
+
+    /// Check that the country of every weapon is in the registry, sorted by weapon id
+    ///
+    /// Weapons without country are ignored, they're already reported by [Self::validate].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use weapons::bullets::{Bullet, BulletType};
+    /// use weapons::country::CountryRegistry;
+    /// use weapons::validation::{Violation, ViolationKind};
+    /// use weapons::WeaponStore;
+    ///
+    /// let mut registry = CountryRegistry::default();
+    /// registry.register("fr".parse().unwrap(), "France");
+    ///
+    /// let mut store = WeaponStore::default();
+    /// let mut bullet = Bullet::new(BulletType::Ordinary);
+    /// bullet.get_informations_mut().country_reference = Some("xx".parse().unwrap());
+    /// store.insert("5.56", bullet);
+    ///
+    /// assert_eq!(
+    ///     store.validate_countries(&registry),
+    ///     vec![Violation {
+    ///         id: "5.56".to_string(),
+    ///         kind: ViolationKind::UnknownCountry("xx".parse().unwrap())
+    ///     }]
+    /// );
+    /// ```
+    pub fn validate_countries(&self, registry: &CountryRegistry) -> Vec<Violation> {
+        let mut violations: Vec<Violation> = self
+            .iter()
+            .filter_map(|(id, weapon)| {
+                let country = weapon
+                    .weapon()
+                    .get_informations()
+                    .country_reference
+                    .as_ref()?;
+                (!registry.contains(country)).then(|| Violation {
+                    id: id.clone(),
+                    kind: ViolationKind::UnknownCountry(country.clone()),
+                })
+            })
+            .collect();
+
+        violations.sort();
+        violations
+    }
+}
+
+/// Check the informations shared by every weapon
+fn check_informations(
+    id: &WeaponID,
+    informations: &WeaponInformations,
+    violations: &mut Vec<Violation>,
+) {
+    let mut push = |kind| {
+        violations.push(Violation {
+            id: id.clone(),
+            kind,
+        })
+    };
+
+    if informations.country_reference.is_none() {
+        push(ViolationKind::MissingCountry);
+    }
+    if informations.range < 0.0 {
+        push(ViolationKind::NegativeRange);
+    }
+    if informations.caliber < 0.0 {
+        push(ViolationKind::NegativeCaliber);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    fn bullet(country: &str) -> crate::bullets::Bullet {
+        use crate::bullets::{Bullet, BulletType};
+
+        let mut bullet = Bullet::new(BulletType::Ordinary);
+        bullet.get_informations_mut().country_reference = Some(country.parse().unwrap());
+        bullet
+    }
+
+    #[test]
+    fn test_valid_store() {
+        use super::*;
+        use crate::firearm::{FireArm, FireArmType};
+
+        let mut store = WeaponStore::default();
+        store.add_bullet("5.56", bullet("fr"));
+
+        let mut famas = FireArm::new(FireArmType::Assault, "5.56");
+        famas.get_informations_mut().country_reference = Some("fr".parse().unwrap());
+        famas.add_allowed_bullet("5.56");
+        store.add_firearm("famas", famas);
+
+        assert!(store.validate().is_empty());
+    }
+
+    #[test]
+    fn test_invalid_informations() {
+        use super::*;
+        use crate::shells::{Shell, ShellType};
+
+        let mut store = WeaponStore::default();
+
+        let mut shell = Shell::new(ShellType::Mortar);
+        shell.get_informations_mut().range = -1.0;
+        shell.get_informations_mut().caliber = -81.0;
+        shell.get_informations_mut().speed = -1.0;
+        store.add_shell("mortar", shell);
+
+        assert_eq!(
+            store.validate(),
+            vec![
+                Violation {
+                    id: "mortar".to_string(),
+                    kind: ViolationKind::MissingCountry
+                },
+                Violation {
+                    id: "mortar".to_string(),
+                    kind: ViolationKind::NegativeRange
+                },
+                Violation {
+                    id: "mortar".to_string(),
+                    kind: ViolationKind::NegativeCaliber
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unknown_and_duplicate_ids() {
+        use super::*;
+        use crate::firearm::{FireArm, FireArmType};
+        use crate::shells::{Shell, ShellType};
+
+        let mut store = WeaponStore::default();
+        store.add_bullet("shared", bullet("fr"));
+
+        let mut shell = Shell::new(ShellType::Mortar);
+        shell.get_informations_mut().country_reference = Some("fr".parse().unwrap());
+        store.add_shell("shared", shell);
+
+        let mut famas = FireArm::new(FireArmType::Assault, "shared");
+        famas.get_informations_mut().country_reference = Some("fr".parse().unwrap());
+        famas.add_allowed_bullet("7.62");
+        store.add_firearm("famas", famas);
+
+        assert_eq!(
+            store.validate(),
+            vec![
+                Violation {
+                    id: "famas".to_string(),
+                    kind: ViolationKind::UnknownBullet("7.62".to_string())
+                },
+                Violation {
+                    id: "shared".to_string(),
+                    kind: ViolationKind::DuplicateId
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,351 @@
+//! This module reload the weapons when their configuration files change
+//!
+//! It allows to tune the weapons without restarting the server. Each reload returns the
+//! difference between the previous and the new weapons.
+
+use std::fmt::{Display, Formatter};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::time::{Duration, Instant};
+
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+
+use crate::loader::{Format, LoadError};
+use crate::{WeaponID, WeaponStore};
+
+/// Time without new event before the files are considered saved
+pub const DEBOUNCE: Duration = Duration::from_millis(100);
+
+/// The weapons that changed between two stores, sorted by id
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct WeaponDiff {
+    pub added: Vec<WeaponID>,
+    pub updated: Vec<WeaponID>,
+    pub removed: Vec<WeaponID>,
+}
+
+impl WeaponDiff {
+    /// Return a boolean that indicates if no weapon changed
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.updated.is_empty() && self.removed.is_empty()
+    }
+}
+
+/// An error that occurred while watching a configuration directory
+#[derive(Debug)]
+pub enum WatchError {
+    /// The directory couldn't be watched
+    Notify(notify::Error),
+    /// The first load of the directory failed
+    Load(Vec<LoadError>),
+}
+
+impl Display for WatchError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WatchError::Notify(e) => write!(f, "{}", e),
+            WatchError::Load(errors) => {
+                let errors: Vec<String> = errors.iter().map(|e| e.to_string()).collect();
+                write!(f, "{}", errors.join("\n"))
+            }
+        }
+    }
+}
+
+impl std::error::Error for WatchError {}
+
+impl WeaponStore {
+    /// Get the weapons added, updated and removed in a new version of the store
+    ///
+    /// A weapon moved to another category is considered updated.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use weapons::shells::{Shell, ShellType};
+    /// use weapons::WeaponStore;
+    ///
+    /// let mut old = WeaponStore::default();
+    /// old.insert("he", Shell::new(ShellType::HighExplosive));
+    /// old.insert("mortar", Shell::new(ShellType::Mortar));
+    ///
+    /// let mut new = old.clone();
+    /// new.insert("ap", Shell::new(ShellType::ArmorPiercing));
+    /// new.remove("mortar");
+    /// new.get_shell_mut("he").unwrap().get_informations_mut().range = 20.0;
+    ///
+    /// let diff = old.diff(&new);
+    /// assert_eq!(diff.added, vec!["ap"]);
+    /// assert_eq!(diff.updated, vec!["he"]);
+    /// assert_eq!(diff.removed, vec!["mortar"]);
+    /// ```
+    pub fn diff(&self, new: &WeaponStore) -> WeaponDiff {
+        let mut diff = WeaponDiff::default();
+
+        for (id, weapon) in new.iter() {
+            match self.get(id.clone()) {
+                None => diff.added.push(id.clone()),
+                Some(previous) if previous != weapon => diff.updated.push(id.clone()),
+                Some(_) => {}
+            }
+        }
+        diff.removed = self
+            .iter()
+            .map(|(id, _)| id)
+            .filter(|id| !new.contains(*id))
+            .cloned()
+            .collect();
+
+        diff.added.sort();
+        diff.updated.sort();
+        diff.removed.sort();
+        diff
+    }
+
+    /// Load a configuration directory and watch it for changes
+    ///
+    /// See [WeaponStore::load_from_dir]
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use std::time::Duration;
+    /// use weapons::WeaponStore;
+    ///
+    /// let mut watcher = WeaponStore::watch("data/config/weapons").unwrap();
+    /// loop {
+    ///     match watcher.wait_reload(Duration::from_secs(1)) {
+    ///         Some(Ok(diff)) => println!("weapons updated: {:?}", diff),
+    ///         Some(Err(errors)) => eprintln!("invalid weapons: {:?}", errors),
+    ///         None => {}
+    ///     }
+    /// }
+    /// ```
+    pub fn watch(path: impl AsRef<Path>) -> Result<WeaponWatcher, WatchError> {
+        let path = path.as_ref().to_path_buf();
+        let store = WeaponStore::load_from_dir(&path).map_err(WatchError::Load)?;
+
+        let (sender, events) = mpsc::channel();
+        // The events are timestamped when they happen, not when the watcher reads them
+        let mut watcher = notify::recommended_watcher(move |event| {
+            let _ = sender.send((Instant::now(), event));
+        })
+        .map_err(WatchError::Notify)?;
+        watcher
+            .watch(&path, RecursiveMode::NonRecursive)
+            .map_err(WatchError::Notify)?;
+
+        Ok(WeaponWatcher {
+            path,
+            store,
+            events,
+            last_change: None,
+            _watcher: watcher,
+        })
+    }
+}
+
+/// A store reloaded when its configuration files change
+///
+/// A single save can produce several events, so the weapons are only reloaded once no file
+/// changed for [DEBOUNCE]. If the new files are invalid, the previous weapons are kept.
+pub struct WeaponWatcher {
+    path: PathBuf,
+    store: WeaponStore,
+    events: Receiver<(Instant, notify::Result<Event>)>,
+    /// The time of the last change that wasn't reloaded yet
+    last_change: Option<Instant>,
+    _watcher: RecommendedWatcher,
+}
+
+impl WeaponWatcher {
+    /// Get the current weapons
+    pub fn get_store(&self) -> &WeaponStore {
+        &self.store
+    }
+
+    /// Get the watched directory
+    pub fn get_path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Reload the weapons if a configuration file changed, without waiting
+    ///
+    /// Return None if nothing changed, or if a file changed less than [DEBOUNCE] ago since it may
+    /// still be written. The change is then reloaded by a later call.
+    pub fn try_reload(&mut self) -> Option<Result<WeaponDiff, Vec<LoadError>>> {
+        while let Ok((time, event)) = self.events.try_recv() {
+            self.record(time, &event);
+        }
+        let settled = self.last_change?.elapsed() >= DEBOUNCE;
+        settled.then(|| self.reload())
+    }
+
+    /// Wait until a configuration file changes, then reload the weapons
+    ///
+    /// Return None if nothing changed before the timeout. If a file changed, the reload waits
+    /// for the files to be saved, so it can return up to [DEBOUNCE] after the timeout.
+    pub fn wait_reload(&mut self, timeout: Duration) -> Option<Result<WeaponDiff, Vec<LoadError>>> {
+        let deadline = Instant::now() + timeout;
+        loop {
+            let now = Instant::now();
+            let wake_up = match self.last_change {
+                Some(last_change) => (last_change + DEBOUNCE).min(deadline + DEBOUNCE),
+                None => deadline,
+            };
+            if now >= wake_up {
+                return self.last_change.is_some().then(|| self.reload());
+            }
+
+            match self.events.recv_timeout(wake_up - now) {
+                Ok((time, event)) => self.record(time, &event),
+                Err(RecvTimeoutError::Timeout) => {}
+                Err(RecvTimeoutError::Disconnected) => {
+                    // No more events can come, a pending change is reloaded once it settled
+                    self.last_change?;
+                    std::thread::sleep(wake_up - now);
+                    return Some(self.reload());
+                }
+            }
+        }
+    }
+
+    /// Reload the weapons now
+    pub fn reload(&mut self) -> Result<WeaponDiff, Vec<LoadError>> {
+        self.last_change = None;
+        let store = WeaponStore::load_from_dir(&self.path)?;
+        let diff = self.store.diff(&store);
+        self.store = store;
+        Ok(diff)
+    }
+
+    /// Remember the time of an event if it modifies a configuration file
+    fn record(&mut self, time: Instant, event: &notify::Result<Event>) {
+        if is_relevant(event) {
+            self.last_change = Some(self.last_change.map_or(time, |last| last.max(time)));
+        }
+    }
+}
+
+/// Check if an event modifies a configuration file
+fn is_relevant(event: &notify::Result<Event>) -> bool {
+    let Ok(event) = event else {
+        return false;
+    };
+    let modified = matches!(
+        event.kind,
+        EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_)
+    );
+    modified && event.paths.iter().any(|p| Format::from_path(p).is_some())
+}
+
+#[cfg(test)]
+mod test {
+    const BULLET: &str = "bullet_type = 0\ndamages = {}\n\
+                          informations = { name = \"5.56\", country_reference = \"fr\" }\n";
+
+    #[test]
+    fn test_reload_on_change() {
+        use super::*;
+        use std::fs;
+
+        let mut dir = std::env::temp_dir();
+        dir.push(format!("aegis-weapons-watch-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("bullets.toml"), format!("[556]\n{}", BULLET)).unwrap();
+
+        let mut watcher = WeaponStore::watch(&dir).unwrap();
+        assert!(watcher.get_store().contains("556"));
+        assert!(watcher.try_reload().is_none());
+
+        fs::write(dir.join("bullets.toml"), format!("[762]\n{}", BULLET)).unwrap();
+        let diff = watcher
+            .wait_reload(Duration::from_secs(5))
+            .expect("no event received")
+            .unwrap();
+        assert_eq!(diff.added, vec!["762"]);
+        assert_eq!(diff.removed, vec!["556"]);
+        assert!(watcher.get_store().contains("762"));
+
+        fs::write(dir.join("bullets.toml"), "[762]\nbullet_type = 42").unwrap();
+        assert!(watcher
+            .wait_reload(Duration::from_secs(5))
+            .unwrap()
+            .is_err());
+        assert!(watcher.get_store().contains("762"));
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_wait_reload_respects_timeout() {
+        use super::*;
+        use std::fs;
+
+        let mut dir = std::env::temp_dir();
+        dir.push(format!("aegis-weapons-watch-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("bullets.toml"), format!("[556]\n{}", BULLET)).unwrap();
+        let mut watcher = WeaponStore::watch(&dir).unwrap();
+
+        // unrelated files keep changing while waiting
+        let noise_dir = dir.clone();
+        let noise = std::thread::spawn(move || {
+            for i in 0..30 {
+                fs::write(noise_dir.join("README.md"), i.to_string()).unwrap();
+                std::thread::sleep(Duration::from_millis(20));
+            }
+        });
+
+        let start = Instant::now();
+        assert!(watcher.wait_reload(Duration::from_millis(200)).is_none());
+        assert!(start.elapsed() < Duration::from_millis(400));
+        noise.join().unwrap();
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_try_reload_waits_for_the_debounce() {
+        use super::*;
+        use std::fs;
+
+        let mut dir = std::env::temp_dir();
+        dir.push(format!("aegis-weapons-watch-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("bullets.toml"), format!("[556]\n{}", BULLET)).unwrap();
+        let mut watcher = WeaponStore::watch(&dir).unwrap();
+
+        fs::write(dir.join("bullets.toml"), format!("[762]\n{}", BULLET)).unwrap();
+        // wait for the event, but less than the debounce
+        std::thread::sleep(DEBOUNCE / 4);
+        assert!(watcher.try_reload().is_none());
+
+        std::thread::sleep(DEBOUNCE * 2);
+        let diff = watcher.try_reload().expect("no event received").unwrap();
+        assert_eq!(diff.added, vec!["762"]);
+        assert!(watcher.try_reload().is_none());
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_try_reload_on_the_first_poll_after_the_debounce() {
+        use super::*;
+        use std::fs;
+
+        let mut dir = std::env::temp_dir();
+        dir.push(format!("aegis-weapons-watch-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("bullets.toml"), format!("[556]\n{}", BULLET)).unwrap();
+        let mut watcher = WeaponStore::watch(&dir).unwrap();
+
+        // the change is only read by the poll, after it settled
+        fs::write(dir.join("bullets.toml"), format!("[762]\n{}", BULLET)).unwrap();
+        std::thread::sleep(DEBOUNCE * 2);
+        let diff = watcher.try_reload().expect("no event received").unwrap();
+        assert_eq!(diff.added, vec!["762"]);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}